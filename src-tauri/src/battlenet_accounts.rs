@@ -0,0 +1,71 @@
+use serde::Serialize;
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::config_backup;
+
+/// `%APPDATA%\Battle.net\Battle.net.config`
+pub fn config_path() -> PathBuf {
+    let app_data = std::env::var("APPDATA").unwrap_or_default();
+    PathBuf::from(app_data).join("Battle.net").join("Battle.net.config")
+}
+
+/// Remembered account names (emails or BattleTags), the one that logs in
+/// next comes first.
+pub fn saved_accounts(path: &Path) -> Result<Vec<String>, String> {
+    let config = read_config(path)?;
+    Ok(account_list(&config))
+}
+
+/// Moves `account_name` to the front of `SavedAccountNames` so Battle.net
+/// picks it on its next start. Every other setting is kept as it was.
+pub fn move_to_front(path: &Path, account_name: &str) -> Result<Vec<String>, String> {
+    let mut config = read_config(path)?;
+
+    let mut accounts = account_list(&config);
+    let index = accounts
+        .iter()
+        .position(|account| account.eq_ignore_ascii_case(account_name))
+        .ok_or_else(|| format!("Battle.net has no saved account named {}", account_name))?;
+    if index == 0 {
+        return Ok(accounts);
+    }
+    let account = accounts.remove(index);
+    accounts.insert(0, account);
+
+    let client = config
+        .get_mut("Client")
+        .and_then(Value::as_object_mut)
+        .ok_or("Battle.net.config has no Client section")?;
+    client.insert("SavedAccountNames".to_string(), Value::String(accounts.join(",")));
+
+    config_backup::write_config(path, to_config_string(&config)?.as_bytes())?;
+    Ok(accounts)
+}
+
+fn read_config(path: &Path) -> Result<Value, String> {
+    let content = fs::read_to_string(path).map_err(|e| format!("Failed to read Battle.net.config: {}", e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Invalid Battle.net.config: {}", e))
+}
+
+fn account_list(config: &Value) -> Vec<String> {
+    config
+        .pointer("/Client/SavedAccountNames")
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|account| !account.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+// Same layout Battle.net writes: four-space indentation
+fn to_config_string(config: &Value) -> Result<String, String> {
+    let mut out = Vec::new();
+    let formatter = serde_json::ser::PrettyFormatter::with_indent(b"    ");
+    let mut serializer = serde_json::Serializer::with_formatter(&mut out, formatter);
+    config.serialize(&mut serializer).map_err(|e| e.to_string())?;
+    String::from_utf8(out).map_err(|e| e.to_string())
+}
@@ -0,0 +1,273 @@
+use serde::Serialize;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Copies kept per config file; older ones are deleted.
+const MAX_BACKUPS: usize = 10;
+
+/// Stores the original location inside each file's backup folder.
+const SOURCE_FILE: &str = "source.txt";
+
+/// Appended to the timestamp of each copy. Two writes in the same millisecond
+/// (an edit followed by its verify rewrite) would otherwise share one name.
+static SEQUENCE: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ConfigBackup {
+    /// `<folder>/<file>`, passed back to `restore`
+    pub id: String,
+    pub original_path: String,
+    /// Milliseconds since the Unix epoch
+    pub created: u64,
+    pub size: u64,
+}
+
+fn backups_dir() -> PathBuf {
+    crate::get_app_data_dir().join("backups")
+}
+
+/// Replaces a launcher config file: the current version is backed up first,
+/// then the new content is written to a temp file next to it and renamed
+/// over the original, so a crash never leaves a half-written file.
+pub fn write_config(path: &Path, contents: &[u8]) -> Result<(), String> {
+    if path.exists() {
+        backup(path)?;
+    }
+    atomic_write(path, contents)
+}
+
+/// Writes to a temp file next to `path` and renames it over, so readers never
+/// see a half-written file. No backup is taken.
+pub fn atomic_write(path: &Path, contents: &[u8]) -> Result<(), String> {
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| format!("Invalid path: {}", path.display()))?
+        .to_string_lossy();
+    let tmp_path = path.with_file_name(format!(".{}.tmp", file_name));
+
+    let write = || -> std::io::Result<()> {
+        let mut file = fs::File::create(&tmp_path)?;
+        file.write_all(contents)?;
+        file.sync_all()
+    };
+    if let Err(e) = write() {
+        let _ = fs::remove_file(&tmp_path);
+        return Err(format!("Failed to write {}: {}", tmp_path.display(), e));
+    }
+
+    fs::rename(&tmp_path, path).map_err(|e| {
+        let _ = fs::remove_file(&tmp_path);
+        format!("Failed to replace {}: {}", path.display(), e)
+    })
+}
+
+/// Copies the file into its backup folder and drops the oldest copies.
+fn backup(path: &Path) -> Result<PathBuf, String> {
+    let dir = backups_dir().join(folder_name(path));
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    fs::write(dir.join(SOURCE_FILE), path.to_string_lossy().as_bytes()).map_err(|e| e.to_string())?;
+
+    let millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or(0);
+    let sequence = SEQUENCE.fetch_add(1, Ordering::Relaxed);
+    let file_name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    let backup_path = dir.join(format!("{}-{}_{}", millis, sequence, file_name));
+    fs::copy(path, &backup_path).map_err(|e| format!("Failed to back up {}: {}", path.display(), e))?;
+
+    let mut copies = backup_files(&dir);
+    while copies.len() > MAX_BACKUPS {
+        let oldest = copies.remove(0);
+        let _ = fs::remove_file(oldest.1);
+    }
+
+    Ok(backup_path)
+}
+
+/// Every backup, newest first.
+pub fn list_backups() -> Vec<ConfigBackup> {
+    let Ok(folders) = fs::read_dir(backups_dir()) else {
+        return Vec::new();
+    };
+
+    let mut backups = Vec::new();
+    for folder in folders.flatten().map(|entry| entry.path()).filter(|path| path.is_dir()) {
+        let Ok(original_path) = fs::read_to_string(folder.join(SOURCE_FILE)) else { continue };
+        let folder_name = folder.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+
+        for (created, path) in backup_files(&folder) {
+            backups.push(ConfigBackup {
+                id: format!("{}/{}", folder_name, path.file_name().unwrap_or_default().to_string_lossy()),
+                original_path: original_path.trim().to_string(),
+                created,
+                size: fs::metadata(&path).map(|m| m.len()).unwrap_or(0),
+            });
+        }
+    }
+    backups.sort_by_key(|backup| std::cmp::Reverse(backup.created));
+    backups
+}
+
+/// Writes a backup back to its original location. The version being replaced
+/// is backed up as well, so a restore can be undone.
+pub fn restore(backup_id: &str) -> Result<String, String> {
+    let (folder, file) = backup_id
+        .split_once('/')
+        .filter(|(folder, file)| is_plain_name(folder) && is_plain_name(file))
+        .ok_or_else(|| format!("Invalid backup id: {}", backup_id))?;
+
+    let dir = backups_dir().join(folder);
+    let original_path = fs::read_to_string(dir.join(SOURCE_FILE))
+        .map_err(|_| format!("Backup folder {} has no source path", folder))?;
+    let original_path = PathBuf::from(original_path.trim());
+
+    let contents = fs::read(dir.join(file)).map_err(|e| format!("Failed to read backup {}: {}", backup_id, e))?;
+    write_config(&original_path, &contents)?;
+    Ok(original_path.to_string_lossy().to_string())
+}
+
+// (created millis, path) of the copies in one folder, oldest first.
+// Names are "<millis>-<sequence>_<file>"; older copies have no sequence.
+fn backup_files(dir: &Path) -> Vec<(u64, PathBuf)> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut files: Vec<(u64, u64, PathBuf)> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let (stamp, _) = name.split_once('_')?;
+            let (millis, sequence) = stamp.split_once('-').unwrap_or((stamp, "0"));
+            Some((millis.parse().ok()?, sequence.parse().ok()?, entry.path()))
+        })
+        .collect();
+    files.sort_by_key(|(created, sequence, _)| (*created, *sequence));
+    files.into_iter().map(|(created, _, path)| (created, path)).collect()
+}
+
+// One readable folder per config file, e.g. "C__Program Files (x86)_Steam_config_loginusers.vdf"
+fn folder_name(path: &Path) -> String {
+    path.to_string_lossy()
+        .chars()
+        .map(|c| if matches!(c, ':' | '\\' | '/') { '_' } else { c })
+        .collect()
+}
+
+// Rejects ids that would escape the backups folder
+fn is_plain_name(name: &str) -> bool {
+    !name.is_empty() && name != "." && name != ".." && !name.contains(['/', '\\', ':'])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A config file in its own temp folder, so each test gets its own backup folder
+    fn config_file(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("config_backup_test_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir.join("loginusers.vdf")
+    }
+
+    fn backups_of(path: &Path) -> Vec<ConfigBackup> {
+        let original = path.to_string_lossy();
+        list_backups().into_iter().filter(|b| b.original_path == original).collect()
+    }
+
+    fn cleanup(path: &Path) {
+        let _ = fs::remove_dir_all(backups_dir().join(folder_name(path)));
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn first_write_has_nothing_to_back_up() {
+        let path = config_file("first");
+        write_config(&path, b"v1").unwrap();
+        let backups = backups_of(&path);
+        let content = fs::read(&path).unwrap();
+        cleanup(&path);
+
+        assert!(backups.is_empty());
+        assert_eq!(content, b"v1");
+    }
+
+    #[test]
+    fn writes_in_the_same_millisecond_keep_separate_copies() {
+        let path = config_file("same_millis");
+        write_config(&path, b"v1").unwrap();
+        write_config(&path, b"v2").unwrap();
+        write_config(&path, b"v3").unwrap();
+        let backups = backups_of(&path);
+        let contents: Vec<Vec<u8>> = backups
+            .iter()
+            .map(|b| fs::read(backups_dir().join(&b.id)).unwrap())
+            .collect();
+        cleanup(&path);
+
+        assert_eq!(backups.len(), 2);
+        assert!(contents.contains(&b"v1".to_vec()) && contents.contains(&b"v2".to_vec()));
+    }
+
+    #[test]
+    fn rotation_keeps_the_newest_copies() {
+        let path = config_file("rotation");
+        for version in 0..=MAX_BACKUPS + 3 {
+            write_config(&path, version.to_string().as_bytes()).unwrap();
+        }
+        let copies = backup_files(&backups_dir().join(folder_name(&path)));
+        let contents: Vec<String> = copies
+            .iter()
+            .map(|(_, copy)| fs::read_to_string(copy).unwrap())
+            .collect();
+        cleanup(&path);
+
+        // Versions 0..=MAX_BACKUPS+2 were backed up, the oldest three rotated out
+        let expected: Vec<String> = (3..=MAX_BACKUPS + 2).map(|v| v.to_string()).collect();
+        assert_eq!(contents, expected);
+    }
+
+    #[test]
+    fn restore_writes_the_copy_back_and_backs_up_the_current_file() {
+        let path = config_file("restore");
+        write_config(&path, b"original").unwrap();
+        write_config(&path, b"edited").unwrap();
+        let id = backups_of(&path)[0].id.clone();
+
+        let restored_to = restore(&id).unwrap();
+        let content = fs::read(&path).unwrap();
+        let backups = backups_of(&path);
+        let backed_up: Vec<Vec<u8>> = backups
+            .iter()
+            .map(|b| fs::read(backups_dir().join(&b.id)).unwrap())
+            .collect();
+        cleanup(&path);
+
+        assert_eq!(restored_to, path.to_string_lossy());
+        assert_eq!(content, b"original");
+        // The edited version can be restored in turn
+        assert!(backed_up.contains(&b"edited".to_vec()));
+    }
+
+    #[test]
+    fn restore_rejects_ids_outside_the_backups_folder() {
+        let ids = ["", "no_separator", "../loginusers.vdf", "folder/../../x", "folder/..", "C:/file", "folder/a\\b"];
+        for id in ids {
+            let error = restore(id).unwrap_err();
+            assert!(error.starts_with("Invalid backup id"), "{}: {}", id, error);
+        }
+    }
+
+    #[test]
+    fn plain_names_have_no_separators() {
+        assert!(is_plain_name("1700000000000-0_loginusers.vdf"));
+        assert!(is_plain_name("C__Program Files (x86)_Steam_config_loginusers.vdf"));
+        for name in ["", ".", "..", "a/b", "a\\b", "C:"] {
+            assert!(!is_plain_name(name), "{}", name);
+        }
+    }
+}
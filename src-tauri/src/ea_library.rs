@@ -0,0 +1,350 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::games::{self, Game, GameExtras, GameLibraryProvider};
+use crate::library_cache;
+
+#[cfg(target_os = "windows")]
+use winreg::enums::*;
+#[cfg(target_os = "windows")]
+use winreg::RegKey;
+
+/// Default EA App library folder, scanned in addition to the registry.
+pub const DEFAULT_LIBRARY: &str = r"C:\Program Files\EA Games";
+
+#[derive(Debug, Clone)]
+pub struct EaGame {
+    pub name: String,
+    pub install_path: PathBuf,
+    pub executable: Option<PathBuf>,
+    /// First contentID / offer id from installerdata.xml
+    pub content_id: Option<String>,
+}
+
+impl EaGame {
+    /// Content id when known, otherwise the install folder name
+    pub fn id(&self) -> String {
+        game_id(&self.install_path, self.content_id.as_deref())
+    }
+}
+
+fn game_id(install_path: &Path, content_id: Option<&str>) -> String {
+    content_id.map(str::to_string).unwrap_or_else(|| folder_name(install_path))
+}
+
+fn folder_name(dir: &Path) -> String {
+    dir.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+/// `origin2://` is still handled by the EA App
+pub fn launch_uri(content_id: &str) -> String {
+    format!("origin2://game/launch?offerIds={}", content_id)
+}
+
+pub struct EaLibrary;
+
+impl GameLibraryProvider for EaLibrary {
+    fn launcher_id(&self) -> &'static str {
+        "EA"
+    }
+
+    fn games(&self) -> Result<Vec<Game>, String> {
+        Ok(read_installed_games().into_iter().map(Game::from).collect())
+    }
+
+    fn launch(&self, native_id: &str) -> Result<(), String> {
+        let game = read_installed_games()
+            .into_iter()
+            .find(|g| g.id() == native_id)
+            .ok_or_else(|| format!("EA game not found: {}", native_id))?;
+
+        match (&game.content_id, &game.executable) {
+            (Some(content_id), _) => games::open_launch_uri(&launch_uri(content_id)),
+            // No offer id in the manifest, start the exe directly
+            (None, Some(exe)) => {
+                std::process::Command::new(exe)
+                    .current_dir(&game.install_path)
+                    .spawn()
+                    .map_err(|e| format!("Failed to launch game: {}", e))?;
+                Ok(())
+            }
+            (None, None) => Err(format!("No executable found for: {}", game.name)),
+        }
+    }
+}
+
+impl From<EaGame> for Game {
+    fn from(game: EaGame) -> Self {
+        Game {
+            launcher_id: "EA".to_string(),
+            native_id: game.id(),
+            name: game.name,
+            install_path: game.install_path.to_string_lossy().to_string(),
+            executables: game
+                .executable
+                .map(|p| p.to_string_lossy().to_string())
+                .into_iter()
+                .collect(),
+            size_on_disk: None,
+            last_played: None,
+            extras: GameExtras::Ea {
+                content_id: game.content_id,
+            },
+        }
+    }
+}
+
+/// Games from the EA registry entries and the default library folder.
+pub fn read_installed_games() -> Vec<EaGame> {
+    let installs: Vec<(Option<String>, PathBuf, InstallerData)> = install_dirs()
+        .into_iter()
+        .map(|(registry_name, dir)| {
+            let installer_data = read_installer_data(&dir);
+            (registry_name, dir, installer_data)
+        })
+        .collect();
+
+    // Only games whose manifest names no existing exe need the folder search
+    let unnamed: Vec<PathBuf> = installs
+        .iter()
+        .filter(|(_, dir, data)| launcher_file(dir, data).is_none())
+        .map(|(_, dir, _)| dir.clone())
+        .collect();
+    let guessed = library_cache::guess_main_executables(&unnamed);
+
+    let mut games: Vec<EaGame> = installs
+        .into_iter()
+        .map(|(registry_name, dir, installer_data)| read_game(dir, registry_name, installer_data, &guessed))
+        .collect();
+    games.sort_by_key(|g| g.name.to_lowercase());
+    games
+}
+
+/// `EaGame::id` of every installed game. Only reads `installerdata.xml`,
+/// without the executable search `read_installed_games` does.
+pub fn installed_game_ids() -> Vec<String> {
+    install_dirs()
+        .into_iter()
+        .map(|(_, dir)| game_id(&dir, read_installer_data(&dir).content_id.as_deref()))
+        .collect()
+}
+
+// Existing install folders, registry entries first, without duplicates
+fn install_dirs() -> Vec<(Option<String>, PathBuf)> {
+    let mut install_dirs = registry_install_dirs();
+
+    if let Ok(entries) = fs::read_dir(DEFAULT_LIBRARY) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.join("__Installer").is_dir() {
+                install_dirs.push((None, path));
+            }
+        }
+    }
+
+    let mut unique: Vec<(Option<String>, PathBuf)> = Vec::new();
+    for (registry_name, dir) in install_dirs {
+        if !dir.is_dir() {
+            continue;
+        }
+        let key = dir.to_string_lossy().trim_end_matches('\\').to_lowercase();
+        if unique
+            .iter()
+            .any(|(_, d)| d.to_string_lossy().trim_end_matches('\\').to_lowercase() == key)
+        {
+            continue;
+        }
+        unique.push((registry_name, dir));
+    }
+    unique
+}
+
+fn read_installer_data(dir: &Path) -> InstallerData {
+    fs::read_to_string(dir.join("__Installer").join("installerdata.xml"))
+        .ok()
+        .map(|xml| parse_installer_data(&xml))
+        .unwrap_or_default()
+}
+
+// Exe named by the manifest, if it exists
+fn launcher_file(dir: &Path, installer_data: &InstallerData) -> Option<PathBuf> {
+    installer_data
+        .launcher_file
+        .as_ref()
+        .map(|file| dir.join(file))
+        .filter(|path| path.is_file())
+}
+
+fn read_game(
+    dir: PathBuf,
+    registry_name: Option<String>,
+    installer_data: InstallerData,
+    guessed: &HashMap<PathBuf, PathBuf>,
+) -> EaGame {
+    let executable = launcher_file(&dir, &installer_data).or_else(|| guessed.get(&dir).cloned());
+
+    EaGame {
+        name: installer_data.title.or(registry_name).unwrap_or_else(|| folder_name(&dir)),
+        install_path: dir,
+        executable,
+        content_id: installer_data.content_id,
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct InstallerData {
+    pub title: Option<String>,
+    pub content_id: Option<String>,
+    /// Launcher exe relative to the install folder
+    pub launcher_file: Option<String>,
+}
+
+/// Pulls the few fields we need out of `__Installer\installerdata.xml`.
+///
+/// Both manifest generations are handled: the newer `<gameTitle>` layout and
+/// the older `<localeInfo><title>` one. The file path is prefixed with a
+/// registry placeholder like `[HKEY_LOCAL_MACHINE\...\Install Dir]bf1.exe`.
+pub fn parse_installer_data(xml: &str) -> InstallerData {
+    let title = first_tag(xml, "gameTitle").or_else(|| first_tag(xml, "title"));
+    let content_id = first_tag(xml, "contentID");
+
+    let launcher_file = first_tag(xml, "runtime")
+        .and_then(|runtime| first_tag(&runtime, "filePath"))
+        .map(|file_path| match file_path.rfind(']') {
+            Some(end) => file_path[end + 1..].to_string(),
+            None => file_path,
+        })
+        .map(|file| file.trim_start_matches(['\\', '/']).to_string())
+        .filter(|file| !file.is_empty());
+
+    InstallerData { title, content_id, launcher_file }
+}
+
+/// Text content of the first `<tag ...>...</tag>` element.
+fn first_tag(xml: &str, tag: &str) -> Option<String> {
+    let open = format!("<{}", tag);
+    let close = format!("</{}>", tag);
+
+    let mut search_from = 0;
+    while let Some(found) = xml[search_from..].find(&open) {
+        let start = search_from + found;
+        let after_name = start + open.len();
+        // Don't match <titleX> when looking for <title>
+        match xml[after_name..].chars().next() {
+            Some('>') | Some(' ') | Some('\t') | Some('\r') | Some('\n') => {}
+            _ => {
+                search_from = after_name;
+                continue;
+            }
+        }
+        let content_start = after_name + xml[after_name..].find('>')? + 1;
+        let content_end = content_start + xml[content_start..].find(&close)?;
+        let text = decode_entities(xml[content_start..content_end].trim());
+        return if text.is_empty() { None } else { Some(text) };
+    }
+    None
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// (registry name, install dir) from `EA Games` / `Electronic Arts` keys
+fn registry_install_dirs() -> Vec<(Option<String>, PathBuf)> {
+    #[cfg(target_os = "windows")]
+    {
+        let hklm = RegKey::predef(HKEY_LOCAL_MACHINE);
+        let mut dirs = Vec::new();
+
+        for root in [
+            r"SOFTWARE\WOW6432Node\EA Games",
+            r"SOFTWARE\EA Games",
+            r"SOFTWARE\WOW6432Node\Electronic Arts",
+        ] {
+            let Ok(root_key) = hklm.open_subkey(root) else { continue };
+            for name in root_key.enum_keys().flatten() {
+                let Ok(game_key) = root_key.open_subkey(&name) else { continue };
+                let install_dir = game_key
+                    .get_value::<String, _>("Install Dir")
+                    .or_else(|_| game_key.get_value::<String, _>("InstallDir"));
+                if let Ok(install_dir) = install_dir {
+                    dirs.push((Some(name), PathBuf::from(install_dir)));
+                }
+            }
+        }
+
+        dirs
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        Vec::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BATTLEFIELD_1: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<DiPManifest version="4.0">
+    <contentIDs>
+        <contentID>1026023</contentID>
+        <contentID>1032756</contentID>
+    </contentIDs>
+    <gameTitles>
+        <gameTitle locale="en_US">Battlefield 1</gameTitle>
+    </gameTitles>
+    <runtime>
+        <launcher uid="launcher-1">
+            <filePath>[HKEY_LOCAL_MACHINE\SOFTWARE\EA Games\Battlefield 1\Install Dir]bf1.exe</filePath>
+            <trial>0</trial>
+        </launcher>
+    </runtime>
+</DiPManifest>
+"#;
+
+    // Older layout: <localeInfo><title> and no runtime section
+    const MASS_EFFECT: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<DiPManifest version="2.0">
+    <contentIDs>
+        <contentID>71184</contentID>
+    </contentIDs>
+    <metadata>
+        <localeInfo locale="en_US">
+            <title>Mass Effect &amp; More</title>
+            <titleSort>Mass Effect</titleSort>
+        </localeInfo>
+    </metadata>
+</DiPManifest>
+"#;
+
+    #[test]
+    fn new_manifest_layout() {
+        let data = parse_installer_data(BATTLEFIELD_1);
+        assert_eq!(data.title.as_deref(), Some("Battlefield 1"));
+        // The first id, not the <contentIDs> wrapper
+        assert_eq!(data.content_id.as_deref(), Some("1026023"));
+        assert_eq!(data.launcher_file.as_deref(), Some("bf1.exe"));
+    }
+
+    #[test]
+    fn old_manifest_layout_without_runtime_launcher() {
+        let data = parse_installer_data(MASS_EFFECT);
+        assert_eq!(data.title.as_deref(), Some("Mass Effect & More"));
+        assert_eq!(data.content_id.as_deref(), Some("71184"));
+        assert_eq!(data.launcher_file, None);
+    }
+
+    #[test]
+    fn launcher_path_without_registry_placeholder() {
+        let xml = "<runtime><launcher><filePath>\\Game\\start.exe</filePath></launcher></runtime>";
+        assert_eq!(parse_installer_data(xml).launcher_file.as_deref(), Some("Game\\start.exe"));
+    }
+}
@@ -0,0 +1,231 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::config_backup;
+
+/// `GameUserSettings.ini` section holding the remembered login token.
+const SESSION_SECTION: &str = "RememberMe";
+
+/// A remembered Epic Games Launcher login, saved under a user-given name.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EpicSession {
+    pub name: String,
+    /// `Identifiers\AccountId` registry value at the time of saving
+    pub account_id: Option<String>,
+    /// Milliseconds since the Unix epoch
+    pub saved_at: u64,
+    /// `[RememberMe]` entries in file order
+    pub remember_me: Vec<(String, String)>,
+}
+
+fn sessions_dir() -> PathBuf {
+    crate::get_app_data_dir().join("epic_sessions")
+}
+
+// Names are trimmed here so saving, switching and deleting agree on the file
+fn session_path(name: &str) -> Result<PathBuf, String> {
+    let name = name.trim();
+    let valid = !name.is_empty()
+        && name != "."
+        && name != ".."
+        && !name.contains(['/', '\\', ':', '*', '?', '"', '<', '>', '|']);
+    if !valid {
+        return Err(format!("Invalid session name: {}", name));
+    }
+    Ok(sessions_dir().join(format!("{}.json", name)))
+}
+
+/// `GameUserSettings.ini` files of the launcher. Older versions use the
+/// `Windows` folder, newer ones `WindowsEditor`; only existing files are returned.
+fn settings_paths() -> Vec<PathBuf> {
+    let local_app_data = std::env::var("LOCALAPPDATA").unwrap_or_default();
+    let config_dir = PathBuf::from(local_app_data)
+        .join("EpicGamesLauncher")
+        .join("Saved")
+        .join("Config");
+    ["WindowsEditor", "Windows"]
+        .iter()
+        .map(|folder| config_dir.join(folder).join("GameUserSettings.ini"))
+        .filter(|path| path.is_file())
+        .collect()
+}
+
+/// Saves the currently remembered login under `name`, replacing a session of
+/// the same name.
+pub fn save_current(name: &str) -> Result<EpicSession, String> {
+    let path = session_path(name)?;
+
+    let remember_me = settings_paths()
+        .iter()
+        .filter_map(|ini| fs::read_to_string(ini).ok())
+        .map(|content| read_section(&content, SESSION_SECTION))
+        .find(|entries| entries.iter().any(|(key, value)| key.eq_ignore_ascii_case("Data") && !value.is_empty()))
+        .ok_or("No remembered Epic login found, sign in with \"Remember me\" checked first")?;
+
+    let session = EpicSession {
+        name: name.trim().to_string(),
+        account_id: read_account_id(),
+        saved_at: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0),
+        remember_me,
+    };
+
+    fs::create_dir_all(sessions_dir()).map_err(|e| e.to_string())?;
+    let json = serde_json::to_string_pretty(&session).map_err(|e| e.to_string())?;
+    fs::write(&path, json).map_err(|e| format!("Failed to save session: {}", e))?;
+    Ok(session)
+}
+
+/// Saved sessions, by name.
+pub fn list_sessions() -> Vec<EpicSession> {
+    let Ok(entries) = fs::read_dir(sessions_dir()) else {
+        return Vec::new();
+    };
+    let mut sessions: Vec<EpicSession> = entries
+        .flatten()
+        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|entry| fs::read_to_string(entry.path()).ok())
+        .filter_map(|content| serde_json::from_str(&content).ok())
+        .collect();
+    sessions.sort_by_key(|session| session.name.to_lowercase());
+    sessions
+}
+
+pub fn delete_session(name: &str) -> Result<(), String> {
+    let path = session_path(name)?;
+    if !path.exists() {
+        return Err(format!("Session not found: {}", name));
+    }
+    fs::remove_file(&path).map_err(|e| format!("Failed to delete session: {}", e))
+}
+
+/// Writes a saved session back. The launcher must be closed, it keeps its
+/// settings in memory and overwrites the file on exit.
+pub fn restore_session(name: &str) -> Result<EpicSession, String> {
+    let path = session_path(name)?;
+    let content = fs::read_to_string(&path).map_err(|_| format!("Session not found: {}", name))?;
+    let session: EpicSession = serde_json::from_str(&content).map_err(|e| format!("Invalid session file: {}", e))?;
+
+    let paths = settings_paths();
+    if paths.is_empty() {
+        return Err("Epic Games Launcher settings not found".to_string());
+    }
+    for ini in paths {
+        let content = fs::read_to_string(&ini).map_err(|e| format!("Failed to read {}: {}", ini.display(), e))?;
+        let updated = replace_section(&content, SESSION_SECTION, &session.remember_me);
+        if updated != content {
+            config_backup::write_config(&ini, updated.as_bytes())?;
+        }
+    }
+
+    if let Some(account_id) = &session.account_id {
+        write_account_id(account_id)?;
+    }
+    Ok(session)
+}
+
+#[cfg(target_os = "windows")]
+const IDENTIFIERS_KEY: &str = r"Software\Epic Games\Unreal Engine\Identifiers";
+
+fn read_account_id() -> Option<String> {
+    #[cfg(target_os = "windows")]
+    {
+        use winreg::enums::*;
+        use winreg::RegKey;
+        RegKey::predef(HKEY_CURRENT_USER)
+            .open_subkey(IDENTIFIERS_KEY)
+            .and_then(|key| key.get_value::<String, _>("AccountId"))
+            .ok()
+            .filter(|id| !id.is_empty())
+    }
+    #[cfg(not(target_os = "windows"))]
+    None
+}
+
+fn write_account_id(account_id: &str) -> Result<(), String> {
+    #[cfg(target_os = "windows")]
+    {
+        use winreg::enums::*;
+        use winreg::RegKey;
+        let (key, _) = RegKey::predef(HKEY_CURRENT_USER)
+            .create_subkey(IDENTIFIERS_KEY)
+            .map_err(|e| format!("Failed to open Epic registry key: {}", e))?;
+        key.set_value("AccountId", &account_id)
+            .map_err(|e| format!("Failed to set Epic AccountId: {}", e))?;
+    }
+    #[cfg(not(target_os = "windows"))]
+    let _ = account_id;
+    Ok(())
+}
+
+// ============================================
+// INI SECTIONS
+// ============================================
+
+fn section_name(line: &str) -> Option<&str> {
+    line.trim().strip_prefix('[')?.strip_suffix(']')
+}
+
+// key=value entries of one section, comments and blank lines skipped
+fn read_section(content: &str, section: &str) -> Vec<(String, String)> {
+    let mut in_section = false;
+    let mut entries = Vec::new();
+    for line in content.lines() {
+        if let Some(name) = section_name(line) {
+            in_section = name.eq_ignore_ascii_case(section);
+            continue;
+        }
+        if !in_section || line.trim_start().starts_with(';') {
+            continue;
+        }
+        if let Some((key, value)) = line.split_once('=') {
+            entries.push((key.trim().to_string(), value.trim().to_string()));
+        }
+    }
+    entries
+}
+
+// Replaces the entries of one section and leaves every other line untouched.
+// A missing section is appended at the end.
+fn replace_section(content: &str, section: &str, entries: &[(String, String)]) -> String {
+    let newline = if content.contains("\r\n") { "\r\n" } else { "\n" };
+    let body: String = entries
+        .iter()
+        .map(|(key, value)| format!("{}={}{}", key, value, newline))
+        .collect();
+
+    let mut out = String::with_capacity(content.len() + body.len());
+    let mut in_section = false;
+    let mut written = false;
+    for line in content.split_inclusive('\n') {
+        if let Some(name) = section_name(line) {
+            in_section = name.eq_ignore_ascii_case(section);
+            out.push_str(line);
+            if in_section && !written {
+                if !line.ends_with('\n') {
+                    out.push_str(newline);
+                }
+                out.push_str(&body);
+                written = true;
+            }
+            continue;
+        }
+        // Old entries go, blank lines between sections stay
+        if !in_section || line.trim().is_empty() {
+            out.push_str(line);
+        }
+    }
+
+    if !written {
+        if !out.is_empty() && !out.ends_with('\n') {
+            out.push_str(newline);
+        }
+        out.push_str(&format!("[{}]{}", section, newline));
+        out.push_str(&body);
+    }
+    out
+}
@@ -0,0 +1,137 @@
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::games::{self, Game, GameExtras, GameLibraryProvider};
+
+/// One `.item` manifest written by the Epic Games Launcher per installed game.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct EpicManifest {
+    pub display_name: String,
+    pub install_location: String,
+    #[serde(default)]
+    pub launch_executable: String,
+    pub app_name: String,
+    #[serde(default)]
+    pub catalog_namespace: String,
+    #[serde(default)]
+    pub install_size: Option<u64>,
+    /// DLC and add-ons carry the AppName of their base game here
+    #[serde(default)]
+    pub main_game_app_name: Option<String>,
+    #[serde(default, rename = "bIsIncompleteInstall")]
+    pub is_incomplete_install: bool,
+}
+
+impl EpicManifest {
+    /// DLC manifests share the base game's install folder and can't be launched.
+    pub fn is_base_game(&self) -> bool {
+        match &self.main_game_app_name {
+            Some(main) => main.is_empty() || main == &self.app_name,
+            None => true,
+        }
+    }
+
+    /// Absolute path of the launch executable
+    pub fn executable_path(&self) -> Option<PathBuf> {
+        if self.launch_executable.is_empty() {
+            return None;
+        }
+        Some(Path::new(&self.install_location).join(&self.launch_executable))
+    }
+}
+
+/// `com.epicgames.launcher://apps/<AppName>?action=launch`
+pub fn launch_uri(app_name: &str) -> String {
+    format!("com.epicgames.launcher://apps/{}?action=launch", app_name)
+}
+
+/// `%ProgramData%\Epic\EpicGamesLauncher\Data\Manifests`
+pub fn manifests_dir() -> PathBuf {
+    let program_data = std::env::var("ProgramData").unwrap_or_else(|_| r"C:\ProgramData".to_string());
+    PathBuf::from(program_data)
+        .join("Epic")
+        .join("EpicGamesLauncher")
+        .join("Data")
+        .join("Manifests")
+}
+
+/// Reads every installed (base) game from the manifest folder.
+/// Broken manifests are skipped, a missing folder means Epic isn't installed.
+pub fn read_installed_games(dir: &Path) -> Result<Vec<EpicManifest>, String> {
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let entries = fs::read_dir(dir).map_err(|e| format!("Failed to read Epic manifests: {}", e))?;
+    let mut games = Vec::new();
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let is_item = path
+            .extension()
+            .map(|ext| ext.eq_ignore_ascii_case("item"))
+            .unwrap_or(false);
+        if !is_item {
+            continue;
+        }
+
+        match parse_manifest(&path) {
+            Ok(manifest) => {
+                if manifest.is_base_game() && !manifest.is_incomplete_install {
+                    games.push(manifest);
+                }
+            }
+            Err(e) => println!("Skipping Epic manifest {}: {}", path.display(), e),
+        }
+    }
+
+    games.sort_by_key(|g| g.display_name.to_lowercase());
+    Ok(games)
+}
+
+pub struct EpicLibrary;
+
+impl GameLibraryProvider for EpicLibrary {
+    fn launcher_id(&self) -> &'static str {
+        "Epic"
+    }
+
+    fn games(&self) -> Result<Vec<Game>, String> {
+        Ok(read_installed_games(&manifests_dir())?
+            .into_iter()
+            .map(Game::from)
+            .collect())
+    }
+
+    fn launch(&self, native_id: &str) -> Result<(), String> {
+        games::open_launch_uri(&launch_uri(native_id))
+    }
+}
+
+impl From<EpicManifest> for Game {
+    fn from(manifest: EpicManifest) -> Self {
+        Game {
+            launcher_id: "Epic".to_string(),
+            executables: manifest
+                .executable_path()
+                .map(|p| p.to_string_lossy().to_string())
+                .into_iter()
+                .collect(),
+            native_id: manifest.app_name,
+            name: manifest.display_name,
+            install_path: manifest.install_location,
+            size_on_disk: manifest.install_size,
+            last_played: None,
+            extras: GameExtras::Epic {
+                catalog_namespace: manifest.catalog_namespace,
+            },
+        }
+    }
+}
+
+pub fn parse_manifest(path: &Path) -> Result<EpicManifest, String> {
+    let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    serde_json::from_str::<EpicManifest>(&content).map_err(|e| e.to_string())
+}
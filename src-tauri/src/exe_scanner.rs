@@ -0,0 +1,212 @@
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+/// Deepest folder level we descend into below a launcher's install root.
+const MAX_SCAN_DEPTH: usize = 8;
+
+#[derive(Debug, Serialize, Clone)]
+pub struct DiscoveredExecutable {
+    pub path: String,
+    /// Path relative to the scanned root, used to build stable rule names
+    pub relative_path: String,
+    pub file_name: String,
+    pub size: u64,
+    /// Last modification time in seconds since the Unix epoch
+    pub modified: Option<u64>,
+}
+
+/// Launcher install root, derived from the main launcher exe.
+///
+/// Most launchers keep their helpers next to the main exe, but some bury the
+/// exe a few folders deep (Epic) or ship sibling folders (Rockstar Social Club).
+pub fn launcher_install_root(launcher_name: &str, launcher_exe: &Path) -> Option<PathBuf> {
+    let levels_up = match launcher_name {
+        // Epic Games\Launcher\Portal\Binaries\Win64\EpicGamesLauncher.exe
+        "Epic" => 4,
+        // Electronic Arts\EA Desktop\EA Desktop\EADesktop.exe
+        "EA" => 2,
+        // Rockstar Games\Launcher\Launcher.exe (+ Rockstar Games\Social Club)
+        "Rockstar" => 2,
+        _ => 1,
+    };
+
+    let mut root = launcher_exe;
+    for _ in 0..levels_up {
+        root = root.parent()?;
+    }
+    Some(root.to_path_buf())
+}
+
+/// Folders that belong to the launcher's data rather than its own binaries.
+/// Steam keeps every installed game under `steamapps`, which must not be
+/// reported as a Steam helper.
+pub fn excluded_dirs(launcher_name: &str) -> &'static [&'static str] {
+    match launcher_name {
+        "Steam" | "Steam_ALL" => &["steamapps", "userdata", "appcache", "depotcache", "logs", "dumps"],
+        _ => &[],
+    }
+}
+
+/// Redistributable installers shipped with games, never worth a rule.
+pub const GAME_EXCLUDED_DIRS: &[&str] = &["_CommonRedist", "__Installer", "Redist", "Support"];
+
+/// Recursively lists every `.exe` below `root`, sorted by relative path.
+pub fn find_executables(root: &Path, excluded: &[&str]) -> Vec<DiscoveredExecutable> {
+    let mut found = Vec::new();
+    walk_dir(root, root, 0, excluded, &mut found);
+    found.sort_by(|a, b| a.relative_path.to_lowercase().cmp(&b.relative_path.to_lowercase()));
+    found
+}
+
+fn walk_dir(
+    root: &Path,
+    dir: &Path,
+    depth: usize,
+    excluded: &[&str],
+    found: &mut Vec<DiscoveredExecutable>,
+) {
+    if depth > MAX_SCAN_DEPTH {
+        return;
+    }
+
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return, // Access denied etc. - skip silently
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        // symlink_metadata: don't follow junctions/symlinks into other trees
+        let metadata = match fs::symlink_metadata(&path) {
+            Ok(m) => m,
+            Err(_) => continue,
+        };
+
+        if metadata.is_dir() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if depth == 0 && excluded.iter().any(|e| e.eq_ignore_ascii_case(&name)) {
+                continue;
+            }
+            walk_dir(root, &path, depth + 1, excluded, found);
+        } else if metadata.is_file() && is_executable(&path) {
+            let modified = metadata
+                .modified()
+                .ok()
+                .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                .map(|d| d.as_secs());
+            let relative = path.strip_prefix(root).unwrap_or(&path);
+
+            found.push(DiscoveredExecutable {
+                path: path.to_string_lossy().to_string(),
+                relative_path: relative.to_string_lossy().to_string(),
+                file_name: entry.file_name().to_string_lossy().to_string(),
+                size: metadata.len(),
+                modified,
+            });
+        }
+    }
+}
+
+/// Best guess for a game's main exe when no manifest names it: the largest
+/// executable that isn't an obvious installer, crash reporter or updater.
+pub fn guess_main_executable(game_dir: &Path) -> Option<PathBuf> {
+    const HELPER_HINTS: &[&str] = &["unins", "setup", "crash", "report", "update", "redist", "launcher"];
+
+    find_executables(game_dir, GAME_EXCLUDED_DIRS)
+        .into_iter()
+        .filter(|exe| {
+            let name = exe.file_name.to_lowercase();
+            !HELPER_HINTS.iter().any(|hint| name.contains(hint))
+        })
+        .max_by_key(|exe| exe.size)
+        .map(|exe| PathBuf::from(exe.path))
+}
+
+fn is_executable(path: &Path) -> bool {
+    path.extension()
+        .map(|ext| ext.eq_ignore_ascii_case("exe"))
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_root(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("exe_scanner_test_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        root
+    }
+
+    fn write_exe(path: &Path, size: usize) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, vec![0u8; size]).unwrap();
+    }
+
+    fn relative_paths(found: &[DiscoveredExecutable]) -> Vec<String> {
+        found.iter().map(|exe| exe.relative_path.replace('\\', "/")).collect()
+    }
+
+    #[test]
+    fn excluded_dirs_are_skipped_at_the_top_level_only() {
+        let root = temp_root("excluded");
+        write_exe(&root.join("Game.exe"), 10);
+        write_exe(&root.join("_CommonRedist").join("vcredist_x64.exe"), 10);
+        write_exe(&root.join("redist").join("dxsetup.exe"), 10);
+        write_exe(&root.join("Bin").join("Support").join("tool.exe"), 10);
+        write_exe(&root.join("readme.txt"), 10);
+
+        let found = find_executables(&root, GAME_EXCLUDED_DIRS);
+        let _ = fs::remove_dir_all(&root);
+
+        assert_eq!(relative_paths(&found), ["Bin/Support/tool.exe", "Game.exe"]);
+    }
+
+    #[test]
+    fn scan_stops_below_max_depth() {
+        let root = temp_root("depth");
+        let mut deepest_scanned = root.clone();
+        for level in 1..=MAX_SCAN_DEPTH {
+            deepest_scanned = deepest_scanned.join(format!("d{}", level));
+        }
+        write_exe(&deepest_scanned.join("deep.exe"), 10);
+        write_exe(&deepest_scanned.join("too_deep").join("hidden.exe"), 10);
+
+        let found = find_executables(&root, &[]);
+        let _ = fs::remove_dir_all(&root);
+
+        let names: Vec<&str> = found.iter().map(|exe| exe.file_name.as_str()).collect();
+        assert_eq!(names, ["deep.exe"]);
+    }
+
+    #[test]
+    fn main_executable_is_the_largest_non_helper() {
+        let root = temp_root("guess");
+        write_exe(&root.join("Game.exe"), 300);
+        write_exe(&root.join("unins000.exe"), 900);
+        write_exe(&root.join("GameLauncher.exe"), 800);
+        write_exe(&root.join("CrashReporter.exe"), 700);
+        write_exe(&root.join("Bin").join("Tool.exe"), 100);
+        write_exe(&root.join("__Installer").join("Touchup.exe"), 1000);
+
+        let guess = guess_main_executable(&root);
+        let _ = fs::remove_dir_all(&root);
+
+        assert_eq!(guess, Some(root.join("Game.exe")));
+    }
+
+    #[test]
+    fn no_guess_when_only_helpers_exist() {
+        let root = temp_root("helpers");
+        write_exe(&root.join("setup.exe"), 100);
+        write_exe(&root.join("Updater.exe"), 100);
+
+        let guess = guess_main_executable(&root);
+        let _ = fs::remove_dir_all(&root);
+
+        assert_eq!(guess, None);
+    }
+}
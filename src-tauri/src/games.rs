@@ -0,0 +1,87 @@
+use serde::Serialize;
+
+use crate::{ea_library, epic_library, steam_library, steam_shortcuts, ubisoft_library};
+
+/// A game from any launcher's library.
+#[derive(Debug, Serialize, Clone)]
+pub struct Game {
+    /// Launcher id as used by the detection wizard ("Steam_ALL", "Epic", ...)
+    pub launcher_id: String,
+    /// Launcher-native id: Steam app id, Epic AppName, EA content id, ...
+    pub native_id: String,
+    pub name: String,
+    pub install_path: String,
+    /// Main executable first, when known
+    pub executables: Vec<String>,
+    pub size_on_disk: Option<u64>,
+    /// Seconds since the Unix epoch
+    pub last_played: Option<u64>,
+    pub extras: GameExtras,
+}
+
+/// Launcher-specific data that doesn't fit the common model.
+#[derive(Debug, Serialize, Clone)]
+#[serde(tag = "type")]
+pub enum GameExtras {
+    Steam {
+        owner_name: String,
+        account_name: String,
+    },
+    Epic {
+        catalog_namespace: String,
+    },
+    Ea {
+        content_id: Option<String>,
+    },
+    Ubisoft {},
+    SteamShortcut {
+        account_id: u32,
+        launch_options: String,
+    },
+}
+
+/// One launcher's game library.
+pub trait GameLibraryProvider: Send + Sync {
+    /// Same id as `Game::launcher_id`
+    fn launcher_id(&self) -> &'static str;
+
+    fn games(&self) -> Result<Vec<Game>, String>;
+
+    fn launch(&self, native_id: &str) -> Result<(), String>;
+}
+
+pub fn providers() -> Vec<Box<dyn GameLibraryProvider>> {
+    vec![
+        Box::new(steam_library::SteamLibrary),
+        Box::new(steam_shortcuts::SteamShortcutLibrary),
+        Box::new(epic_library::EpicLibrary),
+        Box::new(ea_library::EaLibrary),
+        Box::new(ubisoft_library::UbisoftLibrary),
+    ]
+}
+
+pub fn find_provider(launcher_id: &str) -> Option<Box<dyn GameLibraryProvider>> {
+    // The frontend uses both "Steam" and "Steam_ALL"
+    let launcher_id = if launcher_id == "Steam" { "Steam_ALL" } else { launcher_id };
+    providers().into_iter().find(|p| p.launcher_id() == launcher_id)
+}
+
+/// Games of every provider, sorted by name. A launcher that fails to load is
+/// logged and left out instead of failing the whole list.
+pub fn all_games() -> Vec<Game> {
+    let mut games = Vec::new();
+    for provider in providers() {
+        match provider.games() {
+            Ok(provider_games) => games.extend(provider_games),
+            Err(e) => println!("{} library skipped: {}", provider.launcher_id(), e),
+        }
+    }
+    games.sort_by_key(|g| g.name.to_lowercase());
+    games
+}
+
+/// Hands a launcher protocol URI to the shell, which starts the launcher
+pub fn open_launch_uri(uri: &str) -> Result<(), String> {
+    println!("Opening launch URI: {}", uri);
+    tauri_plugin_opener::open_url(uri, None::<&str>).map_err(|e| format!("Failed to open {}: {}", uri, e))
+}
@@ -2,17 +2,29 @@ use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 use steamlocate::SteamDir;
 
+use crate::pe_info;
+
+#[cfg(target_os = "windows")]
+use std::os::windows::process::CommandExt;
+#[cfg(target_os = "windows")]
+use std::process::Command;
 #[cfg(target_os = "windows")]
 use winreg::enums::*;
 #[cfg(target_os = "windows")]
 use winreg::RegKey;
 
+/// İki aday arasındaki skor farkı bundan azsa kullanıcı seçmeli
+const AMBIGUITY_MARGIN: u8 = 25;
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct DetectedLauncher {
     pub id: String,
     pub name: String,
+    /// En yüksek skorlu adayın yolu
     pub path: Option<String>,
     pub status: DetectionStatus,
+    /// Bulunan tüm adaylar, skora göre azalan sırada
+    pub candidates: Vec<DetectionCandidate>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
@@ -21,6 +33,68 @@ pub enum DetectionStatus {
     Found,       // Otomatik bulundu
     NotFound,    // Bulunamadı, kullanıcı müdahalesi gerekli
     UserSkipped, // Kullanıcı "kurulu değil" dedi
+    Ambiguous,   // Birden fazla aday var, kullanıcı seçmeli
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DetectionCandidate {
+    pub path: String,
+    /// 0-100
+    pub confidence: u8,
+    pub evidence: Vec<DetectionEvidence>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "PascalCase")]
+pub enum DetectionEvidence {
+    Registry,       // Registry kaydı bu yolu gösteriyor
+    RunningProcess, // Çalışan process bu exe'den başlatılmış
+    CommonPath,     // Bilinen varsayılan kurulum yolu
+    PeMatch,        // VERSIONINFO CompanyName beklenen vendor
+}
+
+impl DetectionEvidence {
+    fn weight(self) -> u8 {
+        match self {
+            DetectionEvidence::Registry => 40,
+            DetectionEvidence::RunningProcess => 35,
+            DetectionEvidence::PeMatch => 30,
+            DetectionEvidence::CommonPath => 15,
+        }
+    }
+}
+
+/// Gerçek launcher exe'lerinin VERSIONINFO CompanyName değerleri
+fn expected_vendors(launcher_id: &str) -> &'static [&'static str] {
+    match launcher_id {
+        "Steam_ALL" => &["Valve"],
+        "Epic" => &["Epic Games"],
+        "Ubisoft" => &["Ubisoft"],
+        "EA" => &["Electronic Arts"],
+        "Rockstar" => &["Rockstar Games", "Take-Two"],
+        _ => &[],
+    }
+}
+
+/// VERSIONINFO vendor kontrolü. Sadece `Path::exists()` yetmez - `Launcher.exe` adlı her dosya Rockstar değildir.
+enum VendorCheck {
+    Match,
+    Mismatch,
+    Unknown, // VERSIONINFO yok veya okunamadı
+}
+
+fn check_vendor(path: &Path, launcher_id: &str) -> VendorCheck {
+    let file_name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    match pe_info::read_version_info(path) {
+        Some(info) if info.company_name.is_some() => {
+            if info.is_genuine_launcher(expected_vendors(launcher_id), &file_name) {
+                VendorCheck::Match
+            } else {
+                VendorCheck::Mismatch
+            }
+        }
+        _ => VendorCheck::Unknown,
+    }
 }
 
 /// Ana tespit fonksiyonu - tüm launcher'ları tarar
@@ -34,90 +108,214 @@ pub fn auto_detect_all_launchers() -> Vec<DetectedLauncher> {
     ]
 }
 
+// ============================================
+// CANDIDATE COLLECTION
+// ============================================
+
+/// Tek bir launcher için kaynaklardan gelen yolları toplar ve skorlar
+struct CandidateCollector {
+    launcher_id: &'static str,
+    candidates: Vec<(PathBuf, Vec<DetectionEvidence>)>,
+}
+
+impl CandidateCollector {
+    fn new(launcher_id: &'static str) -> Self {
+        Self { launcher_id, candidates: Vec::new() }
+    }
+
+    fn add(&mut self, path: PathBuf, evidence: DetectionEvidence) {
+        if !path.is_file() {
+            return;
+        }
+        let key = normalize_path(&path);
+        match self.candidates.iter_mut().find(|(p, _)| normalize_path(p) == key) {
+            Some((_, existing)) => {
+                if !existing.contains(&evidence) {
+                    existing.push(evidence);
+                }
+            }
+            None => self.candidates.push((path, vec![evidence])),
+        }
+    }
+
+    fn add_all(&mut self, paths: impl IntoIterator<Item = PathBuf>, evidence: DetectionEvidence) {
+        for path in paths {
+            self.add(path, evidence);
+        }
+    }
+
+    fn finish(self, name: &str) -> DetectedLauncher {
+        let launcher_id = self.launcher_id;
+        let mut candidates: Vec<DetectionCandidate> = self
+            .candidates
+            .into_iter()
+            .filter_map(|(path, mut evidence)| {
+                match check_vendor(&path, launcher_id) {
+                    // Başka bir vendor'un exe'si - aynı isimli sahte dosya
+                    VendorCheck::Mismatch => return None,
+                    VendorCheck::Match => evidence.push(DetectionEvidence::PeMatch),
+                    // VERSIONINFO yoksa sadece dosya adına güvenilmez - registry veya
+                    // çalışan process kanıtı olmadan aday sayılmaz
+                    VendorCheck::Unknown => {
+                        let vouched = evidence.iter().any(|e| {
+                            matches!(e, DetectionEvidence::Registry | DetectionEvidence::RunningProcess)
+                        });
+                        if !expected_vendors(launcher_id).is_empty() && !vouched {
+                            return None;
+                        }
+                    }
+                }
+                let score: u32 = evidence.iter().map(|e| e.weight() as u32).sum();
+                Some(DetectionCandidate {
+                    path: path.to_string_lossy().to_string(),
+                    confidence: score.min(100) as u8,
+                    evidence,
+                })
+            })
+            .collect();
+        candidates.sort_by(|a, b| b.confidence.cmp(&a.confidence));
+
+        let status = match candidates.as_slice() {
+            [] => DetectionStatus::NotFound,
+            [_] => DetectionStatus::Found,
+            [best, second, ..] => {
+                if best.confidence.saturating_sub(second.confidence) >= AMBIGUITY_MARGIN {
+                    DetectionStatus::Found
+                } else {
+                    DetectionStatus::Ambiguous
+                }
+            }
+        };
+
+        DetectedLauncher {
+            id: launcher_id.to_string(),
+            name: name.to_string(),
+            path: candidates.first().map(|c| c.path.clone()),
+            status,
+            candidates,
+        }
+    }
+}
+
+fn normalize_path(path: &Path) -> String {
+    path.to_string_lossy().replace('/', "\\").to_lowercase()
+}
+
+fn common_paths(paths: &[&str]) -> Vec<PathBuf> {
+    paths.iter().map(PathBuf::from).collect()
+}
+
+/// Çalışan process'lerin exe yolları (Get-Process -Name)
+fn running_process_paths(process_name: &str) -> Vec<PathBuf> {
+    #[cfg(target_os = "windows")]
+    {
+        let script = format!(
+            "Get-Process -Name '{}' -ErrorAction SilentlyContinue | Select-Object -ExpandProperty Path",
+            process_name
+        );
+        let output = Command::new("powershell")
+            .args(["-NoProfile", "-NonInteractive", "-Command", &script])
+            .creation_flags(0x08000000) // CREATE_NO_WINDOW
+            .output();
+
+        match output {
+            Ok(o) => String::from_utf8_lossy(&o.stdout)
+                .lines()
+                .map(str::trim)
+                .filter(|l| !l.is_empty())
+                .map(PathBuf::from)
+                .collect(),
+            Err(_) => Vec::new(),
+        }
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        let _ = process_name;
+        Vec::new()
+    }
+}
+
+// ============================================
+// LAUNCHERS
+// ============================================
+
 /// Steam Detection - mevcut steamlocate crate kullanılıyor
 fn detect_steam() -> DetectedLauncher {
-    // 1. steamlocate ile dene
+    let mut collector = CandidateCollector::new("Steam_ALL");
+
+    // 1. steamlocate (registry tabanlı) + registry kayıtları
     if let Ok(steamdir) = SteamDir::locate() {
-        let steam_exe = steamdir.path().join("steam.exe");
-        if steam_exe.exists() {
-            return DetectedLauncher {
-                id: "Steam_ALL".to_string(),
-                name: "Steam".to_string(),
-                path: Some(steam_exe.to_string_lossy().to_string()),
-                status: DetectionStatus::Found,
-            };
-        }
+        collector.add(steamdir.path().join("steam.exe"), DetectionEvidence::Registry);
     }
+    #[cfg(target_os = "windows")]
+    collector.add_all(get_steam_from_registry(), DetectionEvidence::Registry);
+
+    // 2. Çalışan process
+    collector.add_all(running_process_paths("steam"), DetectionEvidence::RunningProcess);
+
+    // 3. Yaygın yollar
+    collector.add_all(
+        common_paths(&[
+            r"C:\Program Files (x86)\Steam\steam.exe",
+            r"D:\Steam\steam.exe",
+            r"E:\Steam\steam.exe",
+            r"C:\Program Files\Steam\steam.exe",
+        ]),
+        DetectionEvidence::CommonPath,
+    );
+
+    collector.finish("Steam")
+}
 
-    // 2. Fallback: yaygın yolları tara
-    let common_paths = [
-        r"C:\Program Files (x86)\Steam\steam.exe",
-        r"D:\Steam\steam.exe",
-        r"E:\Steam\steam.exe",
-        r"C:\Program Files\Steam\steam.exe",
-    ];
-
-    for path_str in common_paths {
-        if Path::new(path_str).exists() {
-            return DetectedLauncher {
-                id: "Steam_ALL".to_string(),
-                name: "Steam".to_string(),
-                path: Some(path_str.to_string()),
-                status: DetectionStatus::Found,
-            };
+#[cfg(target_os = "windows")]
+fn get_steam_from_registry() -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+
+    // HKCU SteamExe: forward slash kullanıyor (c:/program files (x86)/steam/steam.exe)
+    let hkcu = RegKey::predef(HKEY_CURRENT_USER);
+    if let Ok(key) = hkcu.open_subkey(r"Software\Valve\Steam") {
+        if let Ok(exe) = key.get_value::<String, _>("SteamExe") {
+            paths.push(PathBuf::from(exe.replace('/', "\\")));
         }
     }
 
-    DetectedLauncher {
-        id: "Steam_ALL".to_string(),
-        name: "Steam".to_string(),
-        path: None,
-        status: DetectionStatus::NotFound,
+    let hklm = RegKey::predef(HKEY_LOCAL_MACHINE);
+    if let Ok(key) = hklm.open_subkey(r"SOFTWARE\WOW6432Node\Valve\Steam") {
+        if let Ok(install_path) = key.get_value::<String, _>("InstallPath") {
+            paths.push(PathBuf::from(install_path).join("steam.exe"));
+        }
     }
+
+    paths
 }
 
 /// Epic Games Detection
 fn detect_epic() -> DetectedLauncher {
+    let mut collector = CandidateCollector::new("Epic");
+
     // 1. Registry'den dene
     #[cfg(target_os = "windows")]
-    if let Some(path) = get_epic_from_registry() {
-        return DetectedLauncher {
-            id: "Epic".to_string(),
-            name: "Epic Games".to_string(),
-            path: Some(path),
-            status: DetectionStatus::Found,
-        };
-    }
+    collector.add_all(get_epic_from_registry(), DetectionEvidence::Registry);
 
-    // 2. Yaygın yolları tara
-    let common_paths = [
-        r"C:\Program Files (x86)\Epic Games\Launcher\Portal\Binaries\Win64\EpicGamesLauncher.exe",
-        r"D:\Epic Games\Launcher\Portal\Binaries\Win64\EpicGamesLauncher.exe",
-        r"C:\Program Files\Epic Games\Launcher\Portal\Binaries\Win64\EpicGamesLauncher.exe",
-        r"E:\Epic Games\Launcher\Portal\Binaries\Win64\EpicGamesLauncher.exe",
-    ];
-
-    for path_str in common_paths {
-        if Path::new(path_str).exists() {
-            return DetectedLauncher {
-                id: "Epic".to_string(),
-                name: "Epic Games".to_string(),
-                path: Some(path_str.to_string()),
-                status: DetectionStatus::Found,
-            };
-        }
-    }
+    // 2. Çalışan process
+    collector.add_all(running_process_paths("EpicGamesLauncher"), DetectionEvidence::RunningProcess);
 
-    DetectedLauncher {
-        id: "Epic".to_string(),
-        name: "Epic Games".to_string(),
-        path: None,
-        status: DetectionStatus::NotFound,
-    }
+    // 3. Yaygın yolları tara
+    collector.add_all(
+        common_paths(&[
+            r"C:\Program Files (x86)\Epic Games\Launcher\Portal\Binaries\Win64\EpicGamesLauncher.exe",
+            r"D:\Epic Games\Launcher\Portal\Binaries\Win64\EpicGamesLauncher.exe",
+            r"C:\Program Files\Epic Games\Launcher\Portal\Binaries\Win64\EpicGamesLauncher.exe",
+            r"E:\Epic Games\Launcher\Portal\Binaries\Win64\EpicGamesLauncher.exe",
+        ]),
+        DetectionEvidence::CommonPath,
+    );
+
+    collector.finish("Epic Games")
 }
 
 #[cfg(target_os = "windows")]
-fn get_epic_from_registry() -> Option<String> {
+fn get_epic_from_registry() -> Option<PathBuf> {
     let hklm = RegKey::predef(HKEY_LOCAL_MACHINE);
 
     // Try WOW6432Node (64-bit registry for 32-bit apps)
@@ -128,9 +326,7 @@ fn get_epic_from_registry() -> Option<String> {
                 r"{}\Launcher\Portal\Binaries\Win64\EpicGamesLauncher.exe",
                 install_location
             );
-            if Path::new(&exe_path).exists() {
-                return Some(exe_path);
-            }
+            return Some(PathBuf::from(exe_path));
         }
     }
 
@@ -139,84 +335,90 @@ fn get_epic_from_registry() -> Option<String> {
 
 /// Ubisoft Connect Detection
 fn detect_ubisoft() -> DetectedLauncher {
-    let common_paths = [
-        r"C:\Program Files (x86)\Ubisoft\Ubisoft Game Launcher\upc.exe",
-        r"D:\Ubisoft\Ubisoft Game Launcher\upc.exe",
-        r"C:\Program Files\Ubisoft\Ubisoft Game Launcher\upc.exe",
-        r"E:\Ubisoft\Ubisoft Game Launcher\upc.exe",
-    ];
-
-    for path_str in common_paths {
-        if Path::new(path_str).exists() {
-            return DetectedLauncher {
-                id: "Ubisoft".to_string(),
-                name: "Ubisoft Connect".to_string(),
-                path: Some(path_str.to_string()),
-                status: DetectionStatus::Found,
-            };
-        }
-    }
+    let mut collector = CandidateCollector::new("Ubisoft");
 
-    DetectedLauncher {
-        id: "Ubisoft".to_string(),
-        name: "Ubisoft Connect".to_string(),
-        path: None,
-        status: DetectionStatus::NotFound,
-    }
+    #[cfg(target_os = "windows")]
+    collector.add_all(
+        get_install_dir_from_registry(r"SOFTWARE\WOW6432Node\Ubisoft\Launcher", "InstallDir", "upc.exe"),
+        DetectionEvidence::Registry,
+    );
+
+    collector.add_all(running_process_paths("upc"), DetectionEvidence::RunningProcess);
+
+    collector.add_all(
+        common_paths(&[
+            r"C:\Program Files (x86)\Ubisoft\Ubisoft Game Launcher\upc.exe",
+            r"D:\Ubisoft\Ubisoft Game Launcher\upc.exe",
+            r"C:\Program Files\Ubisoft\Ubisoft Game Launcher\upc.exe",
+            r"E:\Ubisoft\Ubisoft Game Launcher\upc.exe",
+        ]),
+        DetectionEvidence::CommonPath,
+    );
+
+    collector.finish("Ubisoft Connect")
 }
 
 /// EA App Detection
 fn detect_ea() -> DetectedLauncher {
-    let common_paths = [
-        r"C:\Program Files\Electronic Arts\EA Desktop\EA Desktop\EADesktop.exe",
-        r"D:\Electronic Arts\EA Desktop\EA Desktop\EADesktop.exe",
-        r"C:\Program Files (x86)\Electronic Arts\EA Desktop\EA Desktop\EADesktop.exe",
-        r"E:\EA Desktop\EA Desktop\EADesktop.exe",
-    ];
-
-    for path_str in common_paths {
-        if Path::new(path_str).exists() {
-            return DetectedLauncher {
-                id: "EA".to_string(),
-                name: "EA App".to_string(),
-                path: Some(path_str.to_string()),
-                status: DetectionStatus::Found,
-            };
+    let mut collector = CandidateCollector::new("EA");
+
+    // ClientPath doğrudan EADesktop.exe'yi gösteriyor
+    #[cfg(target_os = "windows")]
+    {
+        let hklm = RegKey::predef(HKEY_LOCAL_MACHINE);
+        if let Ok(key) = hklm.open_subkey(r"SOFTWARE\Electronic Arts\EA Desktop") {
+            if let Ok(client_path) = key.get_value::<String, _>("ClientPath") {
+                collector.add(PathBuf::from(client_path), DetectionEvidence::Registry);
+            }
         }
     }
 
-    DetectedLauncher {
-        id: "EA".to_string(),
-        name: "EA App".to_string(),
-        path: None,
-        status: DetectionStatus::NotFound,
-    }
+    collector.add_all(running_process_paths("EADesktop"), DetectionEvidence::RunningProcess);
+
+    collector.add_all(
+        common_paths(&[
+            r"C:\Program Files\Electronic Arts\EA Desktop\EA Desktop\EADesktop.exe",
+            r"D:\Electronic Arts\EA Desktop\EA Desktop\EADesktop.exe",
+            r"C:\Program Files (x86)\Electronic Arts\EA Desktop\EA Desktop\EADesktop.exe",
+            r"E:\EA Desktop\EA Desktop\EADesktop.exe",
+        ]),
+        DetectionEvidence::CommonPath,
+    );
+
+    collector.finish("EA App")
 }
 
 /// Rockstar Games Launcher Detection
 fn detect_rockstar() -> DetectedLauncher {
-    let common_paths = [
-        r"C:\Program Files\Rockstar Games\Launcher\Launcher.exe",
-        r"D:\Rockstar Games\Launcher\Launcher.exe",
-        r"C:\Program Files (x86)\Rockstar Games\Launcher\Launcher.exe",
-        r"E:\Rockstar Games\Launcher\Launcher.exe",
-    ];
-
-    for path_str in common_paths {
-        if Path::new(path_str).exists() {
-            return DetectedLauncher {
-                id: "Rockstar".to_string(),
-                name: "Rockstar Games".to_string(),
-                path: Some(path_str.to_string()),
-                status: DetectionStatus::Found,
-            };
-        }
-    }
+    let mut collector = CandidateCollector::new("Rockstar");
 
-    DetectedLauncher {
-        id: "Rockstar".to_string(),
-        name: "Rockstar Games".to_string(),
-        path: None,
-        status: DetectionStatus::NotFound,
-    }
+    #[cfg(target_os = "windows")]
+    collector.add_all(
+        get_install_dir_from_registry(r"SOFTWARE\WOW6432Node\Rockstar Games\Launcher", "InstallFolder", "Launcher.exe"),
+        DetectionEvidence::Registry,
+    );
+
+    // "Launcher" genel bir isim - PE kontrolü başka vendor'ları eliyor
+    collector.add_all(running_process_paths("Launcher"), DetectionEvidence::RunningProcess);
+
+    collector.add_all(
+        common_paths(&[
+            r"C:\Program Files\Rockstar Games\Launcher\Launcher.exe",
+            r"D:\Rockstar Games\Launcher\Launcher.exe",
+            r"C:\Program Files (x86)\Rockstar Games\Launcher\Launcher.exe",
+            r"E:\Rockstar Games\Launcher\Launcher.exe",
+        ]),
+        DetectionEvidence::CommonPath,
+    );
+
+    collector.finish("Rockstar Games")
+}
+
+/// HKLM altındaki bir kurulum klasörü değerinden exe yolu üretir
+#[cfg(target_os = "windows")]
+fn get_install_dir_from_registry(subkey: &str, value: &str, exe_name: &str) -> Option<PathBuf> {
+    let hklm = RegKey::predef(HKEY_LOCAL_MACHINE);
+    let key = hklm.open_subkey(subkey).ok()?;
+    let install_dir = key.get_value::<String, _>(value).ok()?;
+    Some(PathBuf::from(install_dir).join(exe_name))
 }
@@ -11,6 +11,41 @@ use serde::{Deserialize, Serialize};
 mod launcher_detector;
 use launcher_detector::{DetectedLauncher, DetectionStatus};
 
+// Executable Discovery Module
+mod exe_scanner;
+
+// PE Version Info Module
+mod pe_info;
+
+// Steam Network Helper Discovery
+mod steam_helpers;
+
+// Launcher Config Backups
+mod config_backup;
+
+// Account Switching (non-Steam launchers)
+mod battlenet_accounts;
+mod epic_accounts;
+mod profile_snapshots;
+
+// Game Libraries
+mod ea_library;
+mod epic_library;
+mod games;
+mod library_cache;
+mod library_watcher;
+mod steam_appinfo;
+mod steam_id;
+mod steam_libraries;
+mod steam_library;
+mod steam_localconfig;
+mod steam_shortcuts;
+mod ubisoft_library;
+mod vdf_binary;
+mod vdf_text;
+use games::Game;
+use steam_id::SteamId;
+
 // ============================================
 // SETTINGS MANAGEMENT
 // ============================================
@@ -21,6 +56,19 @@ struct LauncherSettings {
     skipped_launchers: Vec<String>,
     wizard_completed: bool,
     last_scan: Option<String>,
+    // Blocked games by game id, with the rules created for them
+    #[serde(default)]
+    game_rules: HashMap<String, GameFirewallEntry>,
+    // loginusers.vdf values replaced for an offline launch, by SteamID64 then
+    // key; None means the key wasn't there
+    #[serde(default)]
+    offline_mode_originals: HashMap<String, HashMap<String, Option<String>>>,
+    // SteamID64s kept by forget_steam_accounts_except_pinned
+    #[serde(default)]
+    pinned_steam_accounts: Vec<String>,
+    // Account and launch defaults by Steam app id
+    #[serde(default)]
+    profile_bindings: HashMap<String, ProfileBinding>,
 }
 
 impl Default for LauncherSettings {
@@ -30,20 +78,56 @@ impl Default for LauncherSettings {
             skipped_launchers: Vec::new(),
             wizard_completed: false,
             last_scan: None,
+            game_rules: HashMap::new(),
+            offline_mode_originals: HashMap::new(),
+            pinned_steam_accounts: Vec::new(),
+            profile_bindings: HashMap::new(),
         }
     }
 }
 
-fn get_settings_path() -> std::path::PathBuf {
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct GameFirewallEntry {
+    name: String,
+    install_path: String,
+    rules: Vec<GameExecutableRule>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct GameExecutableRule {
+    path: String,
+    rule_name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct ProfileBinding {
+    account_name: String,
+    // Launch offline unless the caller says otherwise
+    #[serde(default)]
+    offline: bool,
+    // appinfo launch option index, see get_app_launch_options
+    #[serde(default)]
+    launch_option: Option<u32>,
+}
+
+// Settings, caches and backups all live here
+fn get_app_data_dir() -> std::path::PathBuf {
+    // Tests must never touch the real settings and backups
+    if cfg!(test) {
+        return std::env::temp_dir().join("games-launchers-firewall-test");
+    }
+
     // Tauri v2: Use a static fallback for now.
     // In production, this should be obtained from app_handle during runtime
     let home = std::env::var("USERPROFILE")
         .or_else(|_| std::env::var("HOME"))
         .unwrap_or_else(|_| ".".to_string());
     
-    std::path::PathBuf::from(home)
-        .join(".games-launchers-firewall")
-        .join("launcher_settings.json")
+    std::path::PathBuf::from(home).join(".games-launchers-firewall")
+}
+
+fn get_settings_path() -> std::path::PathBuf {
+    get_app_data_dir().join("launcher_settings.json")
 }
 
 fn load_settings() -> LauncherSettings {
@@ -80,8 +164,24 @@ struct SteamGame {
   id: u32,
   name: String,
   path: String,
+  // Absolute game folder (library root + install_dir)
+  install_path: String,
   owner_name: String,
   account_name: String,
+  // appmanifest_*.acf metadata
+  size_on_disk: Option<u64>,
+  build_id: Option<u64>,
+  last_updated: Option<u64>,
+  states: Vec<steam_library::AppState>,
+  // Not fully installed, update queued or download in progress
+  needs_update: bool,
+  auto_update_behavior: Option<String>,
+  bytes_to_download: Option<u64>,
+  bytes_downloaded: Option<u64>,
+  // Most recent LastPlayed over all accounts
+  last_played: Option<u64>,
+  // Per-account localconfig.vdf data, most recently played first
+  account_playtime: Vec<steam_localconfig::AccountPlaytime>,
 }
 
 
@@ -113,65 +213,138 @@ struct SteamGame {
                 // This means it expected `users` key but didn't find it.
 #[tauri::command]
 async fn get_installed_games() -> Result<Vec<SteamGame>, String> {
-  let mut games = Vec::new();
+  collect_steam_games()
+}
 
-  match SteamDir::locate() {
-    Ok(steamdir) => {
-      // Try to read loginusers.vdf
-      let mut users_map = IndexMap::new();
-      let vdf_path = steamdir.path().join("config").join("loginusers.vdf");
-      
-      if vdf_path.exists() {
-        if let Ok(content) = fs::read_to_string(&vdf_path) {
-            users_map = parse_login_users(&content);
-        }
-      }
+// Forces every appmanifest to be parsed again
+#[tauri::command]
+async fn rescan_steam_library() -> Result<Vec<SteamGame>, String> {
+  library_cache::clear()?;
+  collect_steam_games()
+}
 
-      match steamdir.libraries() {
-        Ok(libraries) => {
-            for library in libraries {
-                match library {
-                    Ok(library) => {
-                        for app in library.apps() {
-                            match app {
-                                Ok(app) => {
-                                    let mut owner = "Unknown".to_string();
-                                    let mut account = "Unknown".to_string();
-                                    
-                                     if let Some(user_id) = app.last_user {
-                                         let id_str = user_id.to_string();
-                                         if let Some(user) = users_map.get(&id_str) {
-                                             owner = user.persona_name.clone().unwrap_or("Unknown".to_string());
-                                             account = user.account_name.clone().unwrap_or("Unknown".to_string());
-                                         }
-                                     }
-
-
-                                    games.push(SteamGame {
-                                        id: app.app_id,
-                                        name: app.name.clone().unwrap_or("Unknown Game".to_string()),
-                                        path: app.install_dir.clone(),
-                                        owner_name: owner,
-                                        account_name: account,
-                                    });
-                                }
-                                Err(_) => continue,
-                            }
-                        }
-                    },
-                    Err(_) => continue,
-                }
-            }
-        },
-        Err(e) => return Err(format!("Failed to read Steam libraries: {}", e)),
-      }
+#[tauri::command]
+async fn get_steam_libraries() -> Result<Vec<steam_libraries::SteamLibraryInfo>, String> {
+    let steam_path = get_steam_path()?;
+    steam_libraries::read_libraries(&steam_path)
+}
+
+// Joins one app's localconfig.vdf activity with the loginusers.vdf names
+fn get_account_playtime(
+    activity: &HashMap<u32, HashMap<u32, steam_localconfig::AppActivity>>,
+    users_map: &IndexMap<String, SteamUser>,
+    app_id: u32,
+) -> Vec<steam_localconfig::AccountPlaytime> {
+    let mut playtime: Vec<steam_localconfig::AccountPlaytime> = activity
+        .iter()
+        .filter_map(|(&account_id, apps)| {
+            let app_activity = apps.get(&app_id)?;
+            let user = users_map.get(&SteamId::from_account_id(account_id).to_string());
+            Some(steam_localconfig::AccountPlaytime {
+                account_id,
+                account_name: user.and_then(|u| u.account_name.clone()),
+                persona_name: user.and_then(|u| u.persona_name.clone()),
+                activity: app_activity.clone(),
+            })
+        })
+        .collect();
+    playtime.sort_by_key(|p| std::cmp::Reverse(p.activity.last_played));
+    playtime
+}
+
+fn collect_steam_games() -> Result<Vec<SteamGame>, String> {
+  let steamdir = SteamDir::locate().map_err(|e| format!("Steam installation not found or error: {}", e))?;
+
+  // Try to read loginusers.vdf
+  let mut users_map = IndexMap::new();
+  let vdf_path = steamdir.path().join("config").join("loginusers.vdf");
+  
+  if vdf_path.exists() {
+    if let Ok(content) = fs::read_to_string(&vdf_path) {
+        users_map = parse_login_users(&content);
     }
-    Err(e) => return Err(format!("Steam installation not found or error: {}", e)),
+  }
+
+  let activity = steam_localconfig::read_all(steamdir.path());
+
+  let libraries: Vec<steamlocate::Library> = steamdir
+      .libraries()
+      .map_err(|e| format!("Failed to read Steam libraries: {}", e))?
+      .filter_map(|library| match library {
+          Ok(library) => Some(library),
+          // Reported with its error by get_steam_libraries
+          Err(e) => {
+              println!("Skipping Steam library: {}", e);
+              None
+          }
+      })
+      .collect();
+
+  let mut games = Vec::new();
+  for app in library_cache::scan(&libraries) {
+      let mut owner = "Unknown".to_string();
+      let mut account = "Unknown".to_string();
+
+      if let Some(user_id) = app.last_user {
+          let id_str = user_id.to_string();
+          if let Some(user) = users_map.get(&id_str) {
+              owner = user.persona_name.clone().unwrap_or("Unknown".to_string());
+              account = user.account_name.clone().unwrap_or("Unknown".to_string());
+          }
+      }
+
+      let states = app.state_flags
+          .map(steam_library::decode_state_flags)
+          .unwrap_or_default();
+      let download_pending = match (app.bytes_to_download, app.bytes_downloaded) {
+          (Some(total), Some(done)) => done < total,
+          (Some(total), None) => total > 0,
+          _ => false,
+      };
+
+      let account_playtime = get_account_playtime(&activity, &users_map, app.app_id);
+
+      games.push(SteamGame {
+          id: app.app_id,
+          name: app.name.unwrap_or("Unknown Game".to_string()),
+          path: app.install_dir,
+          install_path: app.install_path,
+          owner_name: owner,
+          account_name: account,
+          size_on_disk: app.size_on_disk,
+          build_id: app.build_id,
+          last_updated: app.last_updated,
+          needs_update: download_pending || steam_library::needs_update(app.state_flags),
+          states,
+          auto_update_behavior: app.auto_update_behavior,
+          bytes_to_download: app.bytes_to_download,
+          bytes_downloaded: app.bytes_downloaded,
+          last_played: account_playtime.iter().filter_map(|p| p.activity.last_played).max(),
+          account_playtime,
+      });
   }
 
   Ok(games)
 }
 
+// ============================================
+// UNIFIED GAME LIBRARY
+// ============================================
+
+#[tauri::command]
+async fn get_all_games() -> Result<Vec<Game>, String> {
+    Ok(games::all_games())
+}
+
+// Launches a game from the unified list through its launcher.
+// Steam games with account switching still go through launch_game.
+#[tauri::command]
+async fn launch_library_game(launcher_id: String, native_id: String) -> Result<(), String> {
+    let provider = games::find_provider(&launcher_id)
+        .ok_or_else(|| format!("Launching is not supported for: {}", launcher_id))?;
+    provider.launch(&native_id)
+}
+
 #[tauri::command]
 async fn close_splash(window: tauri::Window) {
   // Close splashscreen
@@ -233,27 +406,80 @@ fn get_launcher_path(launcher_name: &str) -> Vec<String> {
             }
         },
         "SteamWebHelper" => {
-             if let Ok(steamdir) = SteamDir::locate() {
-                vec![steamdir.path().join("bin").join("cef").join("cef.win7x64").join("steamwebhelper.exe").to_str().unwrap().to_string()]
-            } else {
-                 vec!["C:\\Program Files (x86)\\Steam\\bin\\cef\\cef.win7x64\\steamwebhelper.exe".to_string()]
+            // The cef.* folder name changes with CEF updates, so search for it
+            let steam_root = SteamDir::locate()
+                .map(|steamdir| steamdir.path().to_path_buf())
+                .unwrap_or_else(|_| std::path::PathBuf::from("C:\\Program Files (x86)\\Steam"));
+            match steam_helpers::find_web_helper(&steam_root) {
+                Some(path) => vec![path.to_string_lossy().to_string()],
+                None => vec![],
             }
         },
         "Ubisoft" => vec!["C:\\Program Files (x86)\\Ubisoft\\Ubisoft Game Launcher\\upc.exe".to_string()],
         "EA" => vec!["C:\\Program Files\\Electronic Arts\\EA Desktop\\EA Desktop\\EADesktop.exe".to_string()],
         "Epic" => vec!["C:\\Program Files (x86)\\Epic Games\\Launcher\\Portal\\Binaries\\Win64\\EpicGamesLauncher.exe".to_string()],
+        "BattleNet" => vec!["C:\\Program Files (x86)\\Battle.net\\Battle.net Launcher.exe".to_string()],
         "Rockstar" => vec![
             "C:\\Program Files\\Rockstar Games\\Launcher\\Launcher.exe".to_string(),
             "C:\\Program Files\\Rockstar Games\\Social Club\\SocialClubHelper.exe".to_string(),
             "C:\\Program Files\\Rockstar Games\\Launcher\\RockstarService.exe".to_string(),
             "C:\\Program Files\\Rockstar Games\\Launcher\\ThirdParty\\Crashpad\\RockstarErrorHandler.exe".to_string(),
-            "D:\\SteamLibrary\\steamapps\\common\\Red Dead Redemption 2\\RDR2.exe".to_string(),
-            "D:\\SteamLibrary\\steamapps\\common\\Red Dead Redemption 2\\PlayRDR2.exe".to_string(),
         ],
         _ => vec![]
     }
 }
 
+// Program path a rule currently applies to, None if the rule doesn't exist.
+fn get_firewall_rule_program(rule_name: &str) -> Option<String> {
+    #[cfg(target_os = "windows")]
+    {
+        let script = format!(
+            "(Get-NetFirewallRule -DisplayName '{}' -ErrorAction Stop | Get-NetFirewallApplicationFilter).Program",
+            rule_name.replace('\'', "''")
+        );
+        let output = Command::new("powershell")
+            .args(["-NoProfile", "-NonInteractive", "-Command", &script])
+            .creation_flags(0x08000000) // CREATE_NO_WINDOW
+            .output()
+            .ok()?;
+
+        if !output.status.success() {
+            return None;
+        }
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(|l| l.trim().to_string())
+            .find(|l| !l.is_empty())
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        let _ = rule_name;
+        None
+    }
+}
+
+// Steam updates move helpers around (new cef.* folder); existing block rules
+// then point to files that no longer run. Re-create them on the new paths.
+fn refresh_steam_helper_rules() -> Vec<String> {
+    let mut updated = Vec::new();
+    for (path, rule_name) in launcher_rule_map("Steam") {
+        if let Some(current) = get_firewall_rule_program(&rule_name) {
+            if !current.eq_ignore_ascii_case(&path) {
+                println!("Stale rule {}: {} -> {}", rule_name, current, path);
+                manage_firewall_rule(&rule_name, "", false);
+                manage_firewall_rule(&rule_name, &path, true);
+                updated.push(rule_name);
+            }
+        }
+    }
+    updated
+}
+
+#[tauri::command]
+async fn refresh_steam_rules() -> Result<Vec<String>, String> {
+    Ok(refresh_steam_helper_rules())
+}
+
 fn check_firewall_rule(rule_name: &str) -> bool {
     #[cfg(target_os = "windows")]
     {
@@ -315,13 +541,10 @@ async fn toggle_launcher_firewall(launcher_name: String, block: bool) -> Result<
     println!("Toggling Firewall for: {} -> Blocked: {}", launcher_name, block);
 
     if launcher_name == "Steam" || launcher_name == "Steam_ALL" {
-        // Special case for Steam to block both exe and webhelper
+        // Special case for Steam to block the exe and every network helper
         // using the specific rule names used in launch_game
-        for path in get_launcher_path("Steam") {
-             manage_firewall_rule("Block Steam Exe", &path, block);
-        }
-        for path in get_launcher_path("SteamWebHelper") {
-             manage_firewall_rule("Block Steam WebHelper", &path, block);
+        for (path, rule_name) in launcher_rule_map("Steam") {
+             manage_firewall_rule(&rule_name, &path, block);
         }
         return Ok("Steam Full firewall rules updated".to_string());
     }
@@ -333,60 +556,343 @@ async fn toggle_launcher_firewall(launcher_name: String, block: bool) -> Result<
             let rule_name = format!("Block {} App {}", launcher_name, i + 1);
             manage_firewall_rule(&rule_name, path, block);
         }
+        if !block {
+            remove_stale_launcher_rules(&launcher_name, paths.len());
+        }
         Ok(format!("{} firewall rules updated ({} executables)", launcher_name, paths.len()))
     } else {
         Err("Launcher paths not found".to_string())
     }
 }
 
+// Numbered rules past the current path list, left over from older versions
+// (e.g. the RDR2 exes that used to be "Block Rockstar App 5" and "App 6").
+// Nothing would ever unblock them otherwise.
+fn remove_stale_launcher_rules(launcher_name: &str, path_count: usize) {
+    let prefix = format!("Block {} App ", launcher_name);
+    for rule_name in list_firewall_rule_names(&prefix) {
+        let stale = rule_name
+            .get(prefix.len()..)
+            .and_then(|number| number.parse::<usize>().ok())
+            .is_some_and(|number| number > path_count);
+        if stale {
+            println!("Removing stale launcher rule: {}", rule_name);
+            manage_firewall_rule(&rule_name, "", false);
+        }
+    }
+}
+
 #[derive(Debug, Serialize)]
 struct LauncherFileStatus {
     path: String,
+    // FileDescription / ProductName from the exe's VERSIONINFO
+    display_name: Option<String>,
     rule_name: String,
     blocked: bool,
 }
 
-#[tauri::command]
-async fn get_launcher_files(launcher_name: String) -> Result<Vec<LauncherFileStatus>, String> {
-    let mut file_statuses = Vec::new();
-
+// (path, rule name) pairs for the executables a launcher is blocked through.
+// Steam keeps the rule names used in launch_game.
+fn launcher_rule_map(launcher_name: &str) -> Vec<(String, String)> {
     if launcher_name == "Steam" || launcher_name == "Steam_ALL" {
-        // Special mapping for Steam
+        let mut rules = Vec::new();
         for path in get_launcher_path("Steam") {
-            file_statuses.push(LauncherFileStatus {
-                path: path.clone(),
-                rule_name: "Block Steam Exe".to_string(),
-                blocked: check_firewall_rule("Block Steam Exe"),
-            });
+            rules.push((path, "Block Steam Exe".to_string()));
         }
-        for path in get_launcher_path("SteamWebHelper") {
-            file_statuses.push(LauncherFileStatus {
-                path: path.clone(),
-                rule_name: "Block Steam WebHelper".to_string(),
-                blocked: check_firewall_rule("Block Steam WebHelper"),
-            });
+        if let Ok(steamdir) = SteamDir::locate() {
+            for helper in steam_helpers::find_steam_helpers(steamdir.path()) {
+                rules.push((helper.path.to_string_lossy().to_string(), helper.rule_name.to_string()));
+            }
         }
+        rules
     } else {
-        let paths = get_launcher_path(&launcher_name);
-         for (i, path) in paths.iter().enumerate() {
-            let rule_name = format!("Block {} App {}", launcher_name, i + 1);
-            file_statuses.push(LauncherFileStatus {
-                path: path.clone(),
-                rule_name: rule_name.clone(),
-                blocked: check_firewall_rule(&rule_name),
-            });
-        }
+        get_launcher_path(launcher_name)
+            .into_iter()
+            .enumerate()
+            .map(|(i, path)| (path, format!("Block {} App {}", launcher_name, i + 1)))
+            .collect()
     }
+}
+
+#[tauri::command]
+async fn get_launcher_files(launcher_name: String) -> Result<Vec<LauncherFileStatus>, String> {
+    let file_statuses = launcher_rule_map(&launcher_name)
+        .into_iter()
+        .map(|(path, rule_name)| LauncherFileStatus {
+            display_name: pe_info::read_version_info(std::path::Path::new(&path))
+                .and_then(|info| info.friendly_name()),
+            blocked: check_firewall_rule(&rule_name),
+            path,
+            rule_name,
+        })
+        .collect();
 
     Ok(file_statuses)
 }
 
+#[tauri::command]
+async fn inspect_executable(path: String) -> Result<pe_info::PeIdentity, String> {
+    pe_info::inspect_file(std::path::Path::new(&path))
+}
+
 #[tauri::command]
 async fn toggle_file_rule(rule_name: String, path: String, block: bool) -> Result<(), String> {
     manage_firewall_rule(&rule_name, &path, block);
     Ok(())
 }
 
+// ============================================
+// EXECUTABLE DISCOVERY
+// ============================================
+
+#[derive(Debug, Serialize)]
+struct LauncherExecutable {
+    path: String,
+    relative_path: String,
+    file_name: String,
+    size: u64,
+    modified: Option<u64>,
+    rule_name: String,
+    blocked: bool,
+}
+
+// Main exe of a launcher: the wizard's custom path wins over the defaults.
+fn resolve_launcher_exe(launcher_name: &str) -> Option<std::path::PathBuf> {
+    let settings_key = if launcher_name == "Steam" { "Steam_ALL" } else { launcher_name };
+    let settings = load_settings();
+    settings
+        .launcher_paths
+        .get(settings_key)
+        .cloned()
+        .flatten()
+        .or_else(|| get_launcher_path(launcher_name).into_iter().next())
+        .map(std::path::PathBuf::from)
+}
+
+// Lists the display names of all rules starting with `prefix` in a single call.
+// Checking hundreds of helpers one by one through check_firewall_rule is too slow.
+fn list_firewall_rule_names(prefix: &str) -> std::collections::HashSet<String> {
+    #[cfg(target_os = "windows")]
+    {
+        let script = format!(
+            "Get-NetFirewallRule -DisplayName '{}*' -ErrorAction SilentlyContinue | Select-Object -ExpandProperty DisplayName",
+            prefix.replace('\'', "''")
+        );
+        let output = Command::new("powershell")
+            .args(["-NoProfile", "-NonInteractive", "-Command", &script])
+            .creation_flags(0x08000000) // CREATE_NO_WINDOW
+            .output();
+
+        match output {
+            Ok(o) => String::from_utf8_lossy(&o.stdout)
+                .lines()
+                .map(|l| l.trim().to_string())
+                .filter(|l| !l.is_empty())
+                .collect(),
+            Err(e) => {
+                println!("PS Rule List Failed: {}", e);
+                std::collections::HashSet::new()
+            }
+        }
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        let _ = prefix;
+        std::collections::HashSet::new()
+    }
+}
+
+#[tauri::command]
+async fn scan_launcher_executables(launcher_name: String) -> Result<Vec<LauncherExecutable>, String> {
+    let launcher_exe = resolve_launcher_exe(&launcher_name)
+        .ok_or_else(|| format!("Launcher path not found for: {}", launcher_name))?;
+    let root = exe_scanner::launcher_install_root(&launcher_name, &launcher_exe)
+        .filter(|root| root.is_dir())
+        .ok_or_else(|| format!("Install folder not found for: {}", launcher_name))?;
+
+    println!("Scanning executables for {} in {}", launcher_name, root.display());
+
+    // Executables already covered by the basic mode rules keep their rule names
+    let known_rules = launcher_rule_map(&launcher_name);
+    let rule_prefix = format!("Block {}", if launcher_name == "Steam_ALL" { "Steam" } else { &launcher_name });
+    let existing_rules = list_firewall_rule_names(&rule_prefix);
+
+    let executables = exe_scanner::find_executables(&root, exe_scanner::excluded_dirs(&launcher_name))
+        .into_iter()
+        .map(|exe| {
+            let rule_name = known_rules
+                .iter()
+                .find(|(path, _)| path.eq_ignore_ascii_case(&exe.path))
+                .map(|(_, rule)| rule.clone())
+                .unwrap_or_else(|| format!("{} {}", rule_prefix, exe.relative_path));
+
+            LauncherExecutable {
+                blocked: existing_rules.contains(&rule_name),
+                rule_name,
+                path: exe.path,
+                relative_path: exe.relative_path,
+                file_name: exe.file_name,
+                size: exe.size,
+                modified: exe.modified,
+            }
+        })
+        .collect();
+
+    Ok(executables)
+}
+
+// ============================================
+// GAME FIREWALL
+// ============================================
+
+#[derive(Debug, Serialize)]
+struct GameFirewallStatus {
+    game_id: String,
+    blocked: bool,
+    executables: Vec<GameExecutableStatus>,
+}
+
+#[derive(Debug, Serialize)]
+struct GameExecutableStatus {
+    path: String,
+    rule_name: String,
+    blocked: bool,
+}
+
+#[tauri::command]
+async fn get_app_launch_options(app_id: u32) -> Result<Vec<steam_appinfo::LaunchOption>, String> {
+    let steam_path = get_steam_path()?;
+    steam_appinfo::read_launch_options(&steam_path, app_id)
+}
+
+// Settings key of a game's rules. Steam games keep the bare app id they were
+// stored under before other launchers could be blocked.
+fn game_rule_key(launcher_id: &str, native_id: &str) -> String {
+    match launcher_id {
+        "Steam" | "Steam_ALL" => native_id.to_string(),
+        _ => format!("{}:{}", launcher_id, native_id),
+    }
+}
+
+fn game_rule_prefix(game_id: &str) -> String {
+    format!("Block Game {} ", game_id)
+}
+
+// Installed game of any launcher, looked up through its library provider
+fn find_library_game(launcher_id: &str, native_id: &str) -> Result<Game, String> {
+    let provider = games::find_provider(launcher_id)
+        .ok_or_else(|| format!("Unknown launcher: {}", launcher_id))?;
+    provider
+        .games()?
+        .into_iter()
+        .find(|game| game.native_id == native_id)
+        .ok_or_else(|| format!("Game {} is not installed", native_id))
+}
+
+// The executables the launcher knows about plus everything in the install
+// folder: a bootstrapper (PlayRDR2.exe) often starts the exe that actually
+// goes online (RDR2.exe). Absolute paths, without duplicates.
+// A non-Steam shortcut's start folder can be anything (Desktop, C:\), so only
+// its own exe is blocked.
+fn game_executables(game: &Game) -> Vec<String> {
+    let install_dir = std::path::Path::new(&game.install_path);
+    let mut paths = game.executables.clone();
+    let scan = game.launcher_id != "Steam_Shortcut";
+    if scan && !game.install_path.is_empty() && install_dir.is_dir() {
+        let scanned = exe_scanner::find_executables(install_dir, exe_scanner::GAME_EXCLUDED_DIRS);
+        paths.extend(scanned.into_iter().map(|exe| exe.path));
+    }
+    let mut seen = std::collections::HashSet::new();
+    paths.retain(|path| seen.insert(path.replace('/', "\\").to_lowercase()));
+    paths
+}
+
+#[tauri::command]
+async fn toggle_game_firewall(launcher_id: String, native_id: String, block: bool) -> Result<String, String> {
+    let game_id = game_rule_key(&launcher_id, &native_id);
+    let mut settings = load_settings();
+
+    // Resolved before any rule is touched, so a lookup failure can't leave
+    // settings listing rules that were already deleted
+    let target = if block { Some(find_library_game(&launcher_id, &native_id)?) } else { None };
+
+    // Always drop the previously created rules first, the exe list may have changed
+    if let Some(entry) = settings.game_rules.remove(&game_id) {
+        for rule in &entry.rules {
+            manage_firewall_rule(&rule.rule_name, "", false);
+        }
+    }
+
+    let Some(game) = target else {
+        save_settings(&settings)?;
+        return Ok(format!("Game {} firewall rules removed", game_id));
+    };
+
+    let paths = game_executables(&game);
+    if paths.is_empty() {
+        save_settings(&settings)?;
+        return Err(format!("No executables found for {}", game.name));
+    }
+
+    // Rule names use the path inside the install folder, so they stay the same
+    // when the library moves to another drive
+    let install_dir = std::path::Path::new(&game.install_path);
+    let prefix = game_rule_prefix(&game_id);
+    let rules: Vec<GameExecutableRule> = paths
+        .into_iter()
+        .map(|path| {
+            let relative = std::path::Path::new(&path)
+                .strip_prefix(install_dir)
+                .ok()
+                .filter(|_| !game.install_path.is_empty())
+                .map(|relative| relative.to_string_lossy().to_string())
+                .unwrap_or_else(|| path.clone());
+            GameExecutableRule { rule_name: format!("{}{}", prefix, relative), path }
+        })
+        .collect();
+
+    for rule in &rules {
+        manage_firewall_rule(&rule.rule_name, &rule.path, true);
+    }
+
+    let count = rules.len();
+    settings.game_rules.insert(game_id, GameFirewallEntry {
+        name: game.name.clone(),
+        install_path: game.install_path.clone(),
+        rules,
+    });
+    save_settings(&settings)?;
+
+    Ok(format!("{} blocked ({} executables)", game.name, count))
+}
+
+#[tauri::command]
+async fn get_game_firewall_status(launcher_id: String, native_id: String) -> Result<GameFirewallStatus, String> {
+    let game_id = game_rule_key(&launcher_id, &native_id);
+    let settings = load_settings();
+
+    let Some(entry) = settings.game_rules.get(&game_id) else {
+        return Ok(GameFirewallStatus { game_id, blocked: false, executables: Vec::new() });
+    };
+
+    let existing_rules = list_firewall_rule_names(&game_rule_prefix(&game_id));
+    let executables: Vec<GameExecutableStatus> = entry
+        .rules
+        .iter()
+        .map(|rule| GameExecutableStatus {
+            path: rule.path.clone(),
+            rule_name: rule.rule_name.clone(),
+            blocked: existing_rules.contains(&rule.rule_name),
+        })
+        .collect();
+
+    Ok(GameFirewallStatus {
+        blocked: executables.iter().any(|exe| exe.blocked),
+        game_id,
+        executables,
+    })
+}
+
 fn get_steam_path() -> Result<std::path::PathBuf, String> {
     let steam_dir = SteamDir::locate().map_err(|e| e.to_string())?;
     Ok(steam_dir.path().to_path_buf())
@@ -418,6 +924,24 @@ struct SteamUserDisplay {
     account_name: String,
     persona_name: String,
     timestamp: u64,
+    pinned: bool,
+    // data:image/png;base64 URL of config/avatarcache/<steamid64>.png
+    avatar: Option<String>,
+    most_recent: bool,
+    remember_password: bool,
+    allow_auto_login: bool,
+    wants_offline_mode: bool,
+    // Steam has a saved login token (config.vdf ConnectCache) for the account
+    has_login_token: bool,
+    // -login with this account will ask for the password
+    password_required: bool,
+}
+
+fn steam_avatar_data_url(steam_path: &std::path::Path, steam_id: &str) -> Option<String> {
+    use base64::Engine;
+    let path = steam_path.join("config").join("avatarcache").join(format!("{}.png", steam_id));
+    let data = fs::read(path).ok()?;
+    Some(format!("data:image/png;base64,{}", base64::engine::general_purpose::STANDARD.encode(data)))
 }
 
 fn parse_login_users(content: &str) -> IndexMap<String, SteamUser> {
@@ -441,28 +965,88 @@ fn parse_login_users(content: &str) -> IndexMap<String, SteamUser> {
     IndexMap::new()
 }
 
+// Raw value of any loginusers.vdf key, typed or not
+fn login_user_value<'a>(user: &'a SteamUser, key: &str) -> Option<&'a str> {
+    match key.to_ascii_lowercase().as_str() {
+        "mostrecent" => user.most_recent.as_deref(),
+        "allowautologin" => user.allow_auto_login.as_deref(),
+        _ => user.extra
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(key))
+            .and_then(|(_, v)| v.as_str()),
+    }
+}
+
+// Edits loginusers.vdf in place through VdfDocument, keeping Steam's formatting.
+// The result is re-parsed with parse_login_users and only written when every
+// account left in the file parses and `check` accepts the new values.
+fn edit_login_users(
+    steam_path: &std::path::Path,
+    edit: impl FnOnce(&mut vdf_text::VdfDocument) -> Result<(), String>,
+    check: impl FnOnce(&IndexMap<String, SteamUser>) -> bool,
+) -> Result<(), String> {
+    let vdf_path = steam_path.join("config").join("loginusers.vdf");
+    if !vdf_path.exists() {
+        return Ok(());
+    }
+    let content = fs::read_to_string(&vdf_path).map_err(|e| format!("Failed to read loginusers.vdf: {}", e))?;
+
+    let mut doc = vdf_text::VdfDocument::parse(&content)?;
+    edit(&mut doc)?;
+    if doc.as_str() == content {
+        return Ok(());
+    }
+
+    let users = parse_login_users(doc.as_str());
+    if users.len() != doc.keys(&["users"]).len() || !check(&users) {
+        return Err("Edited loginusers.vdf failed verification, file left unchanged".to_string());
+    }
+    config_backup::write_config(&vdf_path, doc.as_str().as_bytes())
+}
+
 // 4. Helper to get Account ID from Name
 fn get_user_account_id(account_name: &str) -> Option<u32> {
-    if let Ok(steam_path) = get_steam_path() {
-        let vdf_path = steam_path.join("config").join("loginusers.vdf");
-        if let Ok(content) = fs::read_to_string(&vdf_path) {
-            let map = parse_login_users(&content);
-            for (id64_str, user) in map {
-                if let Some(name) = user.account_name {
-                    if name.eq_ignore_ascii_case(account_name) {
-                        // Convert ID64 -> AccountID (u32)
-                        // SteamID64 = 76561197960265728 + AccountID
-                        if let Ok(id64) = id64_str.parse::<u64>() {
-                            if id64 > 76561197960265728 {
-                                return Some((id64 - 76561197960265728) as u32);
-                            }
-                        }
-                    }
-                }
+    let steam_path = get_steam_path().ok()?;
+    let content = fs::read_to_string(steam_path.join("config").join("loginusers.vdf")).ok()?;
+    let users = parse_login_users(&content);
+    find_login_user(&users, account_name).map(|(steam_id, _)| steam_id.account_id())
+}
+
+// A saved login by account name, or by its id in any SteamId form
+fn find_login_user<'a>(users: &'a IndexMap<String, SteamUser>, account: &str) -> Option<(SteamId, &'a SteamUser)> {
+    let by_name = users.iter().find(|(_, user)| {
+        user.account_name.as_deref().is_some_and(|name| name.eq_ignore_ascii_case(account))
+    });
+    let (steam_id, user) = match by_name {
+        Some(found) => found,
+        None => users.get_key_value(&SteamId::parse(account).ok()?.to_string())?,
+    };
+    Some((SteamId::parse(steam_id).ok()?, user))
+}
+
+// Account name for -login when the caller passed an id; anything unknown is used as is
+fn resolve_account_name(steam_path: &std::path::Path, account: &str) -> String {
+    fs::read_to_string(steam_path.join("config").join("loginusers.vdf"))
+        .ok()
+        .and_then(|content| {
+            let users = parse_login_users(&content);
+            find_login_user(&users, account).and_then(|(_, user)| user.account_name.clone())
+        })
+        .unwrap_or_else(|| account.to_string())
+}
+
+// AccountID (userdata folder name) -> account name, from loginusers.vdf
+fn get_account_names(steam_path: &std::path::Path) -> HashMap<u32, String> {
+    let mut names = HashMap::new();
+    let vdf_path = steam_path.join("config").join("loginusers.vdf");
+    if let Ok(content) = fs::read_to_string(&vdf_path) {
+        for (id64_str, user) in parse_login_users(&content) {
+            if let (Ok(steam_id), Some(name)) = (SteamId::parse(&id64_str), user.account_name) {
+                names.insert(steam_id.account_id(), name);
             }
         }
     }
-    None
+    names
 }
 
 // 5. Registry Polling to Wait for Login
@@ -500,6 +1084,21 @@ fn wait_for_user_login(target_account_id: u32) -> bool {
     true
 }
 
+// AccountID Steam is logged in with right now, 0 or None when it isn't
+fn active_steam_account_id() -> Option<u32> {
+    #[cfg(target_os = "windows")]
+    {
+        use winreg::enums::*;
+        use winreg::RegKey;
+        let hkcu = RegKey::predef(HKEY_CURRENT_USER);
+        hkcu.open_subkey("Software\\Valve\\Steam\\ActiveProcess")
+            .and_then(|key| key.get_value::<u32, _>("ActiveUser"))
+            .ok()
+    }
+    #[cfg(not(target_os = "windows"))]
+    None
+}
+
 #[tauri::command]
 async fn get_steam_users() -> Result<Vec<SteamUserDisplay>, String> {
     let steam_path = get_steam_path()?;
@@ -515,18 +1114,36 @@ async fn get_steam_users() -> Result<Vec<SteamUserDisplay>, String> {
     
     let content = fs::read_to_string(&vdf_path).map_err(|e| e.to_string())?;
     let final_users_map = parse_login_users(&content);
+    let pinned = load_settings().pinned_steam_accounts;
+    let login_tokens = connect_cache_keys(&steam_path);
 
     let mut display_users = Vec::new();
     for (id, user) in final_users_map {
-        if let Some(acc_name) = user.account_name {
-            let ts_str = user.timestamp.unwrap_or("0".to_string());
+        if let Some(acc_name) = user.account_name.clone() {
+            let ts_str = user.timestamp.clone().unwrap_or("0".to_string());
             let ts = ts_str.parse::<u64>().unwrap_or(0);
+            let flag = |key: &str| login_user_value(&user, key) == Some("1");
+
+            let remember_password = flag("RememberPassword");
+            // Without a readable config.vdf, RememberPassword is all we know
+            let has_login_token = match &login_tokens {
+                Some(keys) => keys.iter().any(|key| key.eq_ignore_ascii_case(&connect_cache_key(&acc_name))),
+                None => remember_password,
+            };
             
             display_users.push(SteamUserDisplay {
-                steam_id: id,
                 account_name: acc_name,
-                persona_name: user.persona_name.unwrap_or_else(|| "Unknown".to_string()),
+                persona_name: user.persona_name.clone().unwrap_or_else(|| "Unknown".to_string()),
                 timestamp: ts,
+                pinned: pinned.contains(&id),
+                avatar: steam_avatar_data_url(&steam_path, &id),
+                most_recent: flag("MostRecent"),
+                remember_password,
+                allow_auto_login: flag("AllowAutoLogin"),
+                wants_offline_mode: flag("WantsOfflineMode"),
+                password_required: !(remember_password && has_login_token),
+                has_login_token,
+                steam_id: id,
             });
         }
     }
@@ -534,7 +1151,102 @@ async fn get_steam_users() -> Result<Vec<SteamUserDisplay>, String> {
     Ok(display_users)
 }
 
-fn wait_for_steam_exit() {
+#[derive(Debug, Serialize)]
+struct SteamIdForms {
+    // String, SteamID64 doesn't fit a JS number
+    steam_id64: String,
+    steam_id3: String,
+    steam_id2: String,
+    account_id: u32,
+    // Saved login with this id, if any
+    account_name: Option<String>,
+}
+
+// Normalizes a pasted SteamID64, [U:1:N], STEAM_0:X:N or account id
+#[tauri::command]
+async fn convert_steam_id(steam_id: String) -> Result<SteamIdForms, String> {
+    let id = SteamId::parse(&steam_id)?;
+    let account_name = get_steam_path()
+        .ok()
+        .and_then(|steam_path| fs::read_to_string(steam_path.join("config").join("loginusers.vdf")).ok())
+        .and_then(|content| parse_login_users(&content).get(&id.to_string())?.account_name.clone());
+
+    Ok(SteamIdForms {
+        steam_id64: id.steam_id64().to_string(),
+        steam_id3: id.steam_id3(),
+        steam_id2: id.steam_id2(),
+        account_id: id.account_id(),
+        account_name,
+    })
+}
+
+// ============================================
+// NON-STEAM SHORTCUTS
+// ============================================
+
+#[tauri::command]
+async fn get_steam_shortcuts() -> Result<Vec<steam_shortcuts::SteamShortcut>, String> {
+    let steam_path = get_steam_path()?;
+    let account_names = get_account_names(&steam_path);
+
+    let mut shortcuts = steam_shortcuts::list_all(&steam_path);
+    for shortcut in &mut shortcuts {
+        shortcut.account_name = account_names.get(&shortcut.account_id).cloned();
+    }
+    Ok(shortcuts)
+}
+
+#[tauri::command]
+async fn add_steam_shortcut(
+    account_id: u32,
+    app_name: String,
+    exe: String,
+    start_dir: Option<String>,
+    launch_options: Option<String>,
+) -> Result<steam_shortcuts::SteamShortcut, String> {
+    if app_name.trim().is_empty() || exe.trim().is_empty() {
+        return Err("App name and executable are required".to_string());
+    }
+    let steam_path = get_steam_path()?;
+    let new = steam_shortcuts::NewShortcut {
+        app_name: &app_name,
+        exe: &exe,
+        start_dir: start_dir.as_deref(),
+        launch_options: launch_options.as_deref(),
+    };
+
+    // Don't close Steam for a shortcut that would be refused anyway
+    steam_shortcuts::validate(&steam_path, account_id, &new)?;
+
+    // Steam rewrites shortcuts.vdf from memory on exit, close it first
+    #[cfg(target_os = "windows")]
+    {
+        let _ = Command::new("taskkill").args(["/F", "/IM", "steam.exe"]).creation_flags(0x08000000).output();
+        wait_for_process_exit("steam.exe");
+    }
+
+    let mut shortcut = steam_shortcuts::add(&steam_path, account_id, new)?;
+    shortcut.account_name = get_account_names(&steam_path).get(&account_id).cloned();
+    Ok(shortcut)
+}
+
+#[tauri::command]
+async fn remove_steam_shortcut(account_id: u32, app_id: u32) -> Result<(), String> {
+    let steam_path = get_steam_path()?;
+    if !steam_shortcuts::read_account(&steam_path, account_id)?.iter().any(|s| s.app_id == app_id) {
+        return Err(format!("Shortcut {} not found", app_id));
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        let _ = Command::new("taskkill").args(["/F", "/IM", "steam.exe"]).creation_flags(0x08000000).output();
+        wait_for_process_exit("steam.exe");
+    }
+
+    steam_shortcuts::remove(&steam_path, account_id, app_id)
+}
+
+fn wait_for_process_exit(image_name: &str) {
     let max_retries = 20; // 20 * 500ms = 10 seconds
     let mut retry_count = 0;
 
@@ -544,14 +1256,14 @@ fn wait_for_steam_exit() {
         }
 
         let output = Command::new("tasklist")
-            .args(["/FI", "IMAGENAME eq steam.exe", "/NH"])
+            .args(["/FI", &format!("IMAGENAME eq {}", image_name), "/NH"])
             .creation_flags(0x08000000)
             .output();
 
         match output {
             Ok(o) => {
                 let stdout = String::from_utf8_lossy(&o.stdout);
-                if !stdout.contains("steam.exe") {
+                if !stdout.to_lowercase().contains(&image_name.to_lowercase()) {
                     // Process gone
                     break;
                 }
@@ -572,7 +1284,7 @@ fn set_active_steam_user(account_name: &str) -> Result<(), String> {
     {
         let _ = Command::new("taskkill").args(["/F", "/IM", "steam.exe"]).creation_flags(0x08000000).output();
         // Wait for it to effectively die to make sure the new instance starts cleanly
-        wait_for_steam_exit();
+        wait_for_process_exit("steam.exe");
     }
     
     // User requested to remove File/Registry manipulations as -login argument is sufficient.
@@ -585,11 +1297,14 @@ async fn switch_steam_account(account_name: String) -> Result<(), String> {
         return Err("Invalid account name".to_string());
     }
 
+    // Accepts a SteamID in any form as well as the account name
+    let steam_path = get_steam_path()?;
+    let account_name = resolve_account_name(&steam_path, &account_name);
+
     set_active_steam_user(&account_name)?;
 
     // Wait a bit for Steam to fully die if needed, usually taskkill is fast
     // Then start Steam
-    let steam_path = get_steam_path()?;
     let steam_exe = steam_path.join("steam.exe");
 
     let mut command = Command::new(&steam_exe);
@@ -602,11 +1317,520 @@ async fn switch_steam_account(account_name: String) -> Result<(), String> {
     Ok(())
 }
 
+// ============================================
+// EPIC ACCOUNT SESSIONS
+// ============================================
+
+// Saves the login Epic currently remembers under a name of the user's choosing
+#[tauri::command]
+async fn save_epic_session(name: String) -> Result<epic_accounts::EpicSession, String> {
+    epic_accounts::save_current(&name)
+}
+
+#[tauri::command]
+async fn get_epic_sessions() -> Result<Vec<epic_accounts::EpicSession>, String> {
+    Ok(epic_accounts::list_sessions())
+}
+
+#[tauri::command]
+async fn delete_epic_session(name: String) -> Result<(), String> {
+    epic_accounts::delete_session(&name)
+}
+
+#[tauri::command]
+async fn switch_epic_account(name: String) -> Result<(), String> {
+    // Epic writes its settings back on exit, close it before swapping the session
+    #[cfg(target_os = "windows")]
+    {
+        let _ = Command::new("taskkill").args(["/F", "/IM", "EpicGamesLauncher.exe"]).creation_flags(0x08000000).output();
+        wait_for_process_exit("EpicGamesLauncher.exe");
+    }
+
+    let session = epic_accounts::restore_session(&name)?;
+    println!("Restored Epic session: {}", session.name);
+
+    open_launcher("Epic".to_string()).await
+}
+
+// ============================================
+// LAUNCHER PROFILE SNAPSHOTS
+// ============================================
+
+fn session_spec(launcher_id: &str) -> Result<profile_snapshots::SessionSpec, String> {
+    profile_snapshots::session_spec(launcher_id)
+        .ok_or_else(|| format!("Profile switching is not supported for: {}", launcher_id))
+}
+
+// Snapshots the session the launcher is logged in with right now
+#[tauri::command]
+async fn save_launcher_profile(launcher_id: String, name: String) -> Result<profile_snapshots::ProfileSnapshot, String> {
+    profile_snapshots::save(&session_spec(&launcher_id)?, &name)
+}
+
+#[tauri::command]
+async fn get_launcher_profiles(launcher_id: String) -> Result<Vec<profile_snapshots::ProfileSnapshot>, String> {
+    session_spec(&launcher_id)?;
+    Ok(profile_snapshots::list(&launcher_id))
+}
+
+#[tauri::command]
+async fn delete_launcher_profile(launcher_id: String, name: String) -> Result<(), String> {
+    session_spec(&launcher_id)?;
+    profile_snapshots::delete(&launcher_id, &name)
+}
+
+// Close the launcher, swap the snapshot in, start it again
+async fn switch_launcher_profile(launcher_id: &str, account_name: &str) -> Result<(), String> {
+    let spec = session_spec(launcher_id)?;
+
+    #[cfg(target_os = "windows")]
+    {
+        for &process in spec.processes {
+            let _ = Command::new("taskkill").args(["/F", "/IM", process]).creation_flags(0x08000000).output();
+        }
+        for process in spec.processes {
+            wait_for_process_exit(process);
+        }
+    }
+
+    let snapshot = profile_snapshots::restore(&spec, account_name)?;
+    println!("Restored {} profile: {}", launcher_id, snapshot.name);
+
+    open_launcher(launcher_id.to_string()).await
+}
+
+#[tauri::command]
+async fn switch_ubisoft_account(account_name: String) -> Result<(), String> {
+    switch_launcher_profile("Ubisoft", &account_name).await
+}
+
 #[tauri::command]
-async fn launch_game(app_id: u32, account_name: Option<String>, offline: bool) -> Result<(), String> {
+async fn switch_ea_account(account_name: String) -> Result<(), String> {
+    switch_launcher_profile("EA", &account_name).await
+}
+
+// ============================================
+// BATTLE.NET ACCOUNTS
+// ============================================
+
+// Saved Battle.net logins, the one used on the next start first
+#[tauri::command]
+async fn get_battlenet_accounts() -> Result<Vec<String>, String> {
+    battlenet_accounts::saved_accounts(&battlenet_accounts::config_path())
+}
+
+#[tauri::command]
+async fn switch_battlenet_account(account_name: String) -> Result<(), String> {
+    // Battle.net rewrites its config on exit, close it first
+    #[cfg(target_os = "windows")]
+    {
+        let _ = Command::new("taskkill").args(["/F", "/IM", "Battle.net.exe"]).creation_flags(0x08000000).output();
+        wait_for_process_exit("Battle.net.exe");
+    }
+
+    battlenet_accounts::move_to_front(&battlenet_accounts::config_path(), &account_name)?;
+    println!("Battle.net will log in as: {}", account_name);
+
+    open_launcher("BattleNet".to_string()).await
+}
+
+// ============================================
+// PER-ACCOUNT OFFLINE MODE
+// ============================================
+
+#[derive(Debug, Serialize)]
+struct OfflineModeStatus {
+    steam_id: String,
+    account_name: String,
+    wants_offline_mode: bool,
+    skip_offline_mode_warning: bool,
+    most_recent: bool,
+    // Values were changed by us and will be restored on the next online launch
+    pending_restore: bool,
+}
+
+// SteamID64 of the account Steam will log into: the requested one, else the MostRecent one
+fn offline_target_steam_id(steam_path: &std::path::Path, account_name: Option<&str>) -> Option<String> {
+    let content = fs::read_to_string(steam_path.join("config").join("loginusers.vdf")).ok()?;
+    let users = parse_login_users(&content);
+
+    match account_name.filter(|name| !name.is_empty() && *name != "Unknown") {
+        Some(name) => find_login_user(&users, name).map(|(steam_id, _)| steam_id.to_string()),
+        None => users
+            .iter()
+            .find(|(_, user)| user.most_recent.as_deref() == Some("1"))
+            .map(|(steam_id, _)| steam_id.clone()),
+    }
+}
+
+// Offline mode for one account only, without the "Steam is offline" prompt, and
+// makes it the auto-login account. Replaced values are remembered in settings;
+// a repeated offline launch keeps the first (real) originals.
+fn set_account_offline(steam_path: &std::path::Path, steam_id: &str) -> Result<(), String> {
+    let mut settings = load_settings();
+
+    edit_login_users(
+        steam_path,
+        |doc| {
+            let steam_ids = doc.keys(&["users"]);
+            if !steam_ids.iter().any(|id| id == steam_id) {
+                return Err(format!("Account {} not found in loginusers.vdf", steam_id));
+            }
+            for id in &steam_ids {
+                let changes: &[(&str, bool)] = if id == steam_id {
+                    &[("WantsOfflineMode", true), ("SkipOfflineModeWarning", true), ("MostRecent", true)]
+                } else {
+                    &[("MostRecent", false)]
+                };
+                for &(key, value) in changes {
+                    let path = ["users", id.as_str(), key];
+                    if doc.get_bool(&path) == Some(value) {
+                        continue;
+                    }
+                    settings.offline_mode_originals
+                        .entry(id.clone())
+                        .or_default()
+                        .entry(key.to_string())
+                        .or_insert_with(|| doc.get_str(&path).map(str::to_string));
+                    doc.set_bool(&path, value)?;
+                }
+            }
+            Ok(())
+        },
+        |users| users.get(steam_id).is_some_and(|user| {
+            login_user_value(user, "WantsOfflineMode") == Some("1")
+                && login_user_value(user, "MostRecent") == Some("1")
+        }),
+    )?;
+
+    save_settings(&settings)
+}
+
+// Puts back exactly what set_account_offline replaced, removing keys that
+// weren't there before. Accounts removed from Steam meanwhile are skipped.
+fn restore_offline_mode(steam_path: &std::path::Path) -> Result<(), String> {
+    let mut settings = load_settings();
+    if settings.offline_mode_originals.is_empty() {
+        return Ok(());
+    }
+    // A running Steam writes its own loginusers.vdf back on exit
+    close_steam();
+    let originals = settings.offline_mode_originals.clone();
+
+    edit_login_users(
+        steam_path,
+        |doc| {
+            let steam_ids = doc.keys(&["users"]);
+            for (steam_id, values) in originals.iter().filter(|(id, _)| steam_ids.contains(id)) {
+                for (key, original) in values {
+                    let path = ["users", steam_id.as_str(), key.as_str()];
+                    match original {
+                        Some(value) if doc.get_str(&path) != Some(value.as_str()) => doc.set_str(&path, value)?,
+                        Some(_) => {}
+                        None => {
+                            doc.remove(&path)?;
+                        }
+                    }
+                }
+            }
+            Ok(())
+        },
+        |users| originals.iter().all(|(steam_id, values)| match users.get(steam_id) {
+            Some(user) => values.iter().all(|(key, original)| login_user_value(user, key) == original.as_deref()),
+            None => true,
+        }),
+    )?;
+
+    settings.offline_mode_originals.clear();
+    save_settings(&settings)
+}
+
+#[tauri::command]
+async fn get_offline_mode_status() -> Result<Vec<OfflineModeStatus>, String> {
+    let steam_path = get_steam_path()?;
+    let content = fs::read_to_string(steam_path.join("config").join("loginusers.vdf"))
+        .map_err(|e| format!("Failed to read loginusers.vdf: {}", e))?;
+    let doc = vdf_text::VdfDocument::parse(&content)?;
+    let settings = load_settings();
+
+    Ok(doc.keys(&["users"])
+        .into_iter()
+        .map(|steam_id| {
+            let flag = |key: &str| doc.get_bool(&["users", steam_id.as_str(), key]).unwrap_or(false);
+            OfflineModeStatus {
+                account_name: doc.get_str(&["users", steam_id.as_str(), "AccountName"]).unwrap_or("Unknown").to_string(),
+                wants_offline_mode: flag("WantsOfflineMode"),
+                skip_offline_mode_warning: flag("SkipOfflineModeWarning"),
+                most_recent: flag("MostRecent"),
+                pending_restore: settings.offline_mode_originals.contains_key(&steam_id),
+                steam_id,
+            }
+        })
+        .collect())
+}
+
+// Backups of loginusers.vdf, shortcuts.vdf, ... taken before every edit, newest first
+#[tauri::command]
+async fn list_config_backups() -> Result<Vec<config_backup::ConfigBackup>, String> {
+    Ok(config_backup::list_backups())
+}
+
+// Returns the path that was restored. Steam must be closed, it rewrites its config on exit
+#[tauri::command]
+async fn restore_config_backup(backup_id: String) -> Result<String, String> {
+    let restored = config_backup::restore(&backup_id)?;
+    println!("Restored {} from backup {}", restored, backup_id);
+    Ok(restored)
+}
+
+// ============================================
+// FORGET STEAM ACCOUNTS
+// ============================================
+
+#[derive(Debug, Serialize)]
+struct ForgottenAccount {
+    steam_id: String,
+    account_name: Option<String>,
+    userdata_deleted: bool,
+}
+
+// Removes accounts from loginusers.vdf along with their saved login token
+// (config.vdf ConnectCache) and AutoLoginUser; userdata/<account id> only
+// when asked. Steam must be closed, it rewrites these files on exit.
+fn forget_steam_accounts(
+    steam_path: &std::path::Path,
+    steam_ids: &[String],
+    delete_userdata: bool,
+) -> Result<Vec<ForgottenAccount>, String> {
+    let content = fs::read_to_string(steam_path.join("config").join("loginusers.vdf"))
+        .map_err(|e| format!("Failed to read loginusers.vdf: {}", e))?;
+    let users = parse_login_users(&content);
+    let targets: Vec<(String, Option<String>)> = steam_ids
+        .iter()
+        .filter_map(|steam_id| users.get(steam_id).map(|user| (steam_id.clone(), user.account_name.clone())))
+        .collect();
+    if targets.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    edit_login_users(
+        steam_path,
+        |doc| {
+            for (steam_id, _) in &targets {
+                doc.remove(&["users", steam_id.as_str()])?;
+            }
+            Ok(())
+        },
+        |users| targets.iter().all(|(steam_id, _)| !users.contains_key(steam_id)),
+    )?;
+
+    let account_names: Vec<&str> = targets.iter().filter_map(|(_, name)| name.as_deref()).collect();
+    if let Err(e) = remove_connect_cache(steam_path, &account_names) {
+        println!("ConnectCache not cleaned: {}", e);
+    }
+    clear_auto_login_user(&account_names);
+
+    let mut settings = load_settings();
+    for (steam_id, _) in &targets {
+        settings.offline_mode_originals.remove(steam_id);
+        settings.pinned_steam_accounts.retain(|pinned| pinned != steam_id);
+    }
+    save_settings(&settings)?;
+
+    Ok(targets
+        .into_iter()
+        .map(|(steam_id, account_name)| {
+            let userdata_deleted = delete_userdata && delete_steam_userdata(steam_path, &steam_id);
+            println!("Forgot Steam account {} ({})", account_name.as_deref().unwrap_or("?"), steam_id);
+            ForgottenAccount { steam_id, account_name, userdata_deleted }
+        })
+        .collect())
+}
+
+// Steam keys each remembered login token by crc32(account name) in hex + "1"
+fn connect_cache_key(account_name: &str) -> String {
+    format!("{:x}1", crc32fast::hash(account_name.as_bytes()))
+}
+
+// None when config.vdf can't be read
+fn connect_cache_keys(steam_path: &std::path::Path) -> Option<Vec<String>> {
+    let content = fs::read_to_string(steam_path.join("config").join("config.vdf")).ok()?;
+    let doc = vdf_text::VdfDocument::parse(&content).ok()?;
+    Some(doc.keys(&["InstallConfigStore", "Software", "Valve", "Steam", "ConnectCache"]))
+}
+
+fn remove_connect_cache(steam_path: &std::path::Path, account_names: &[&str]) -> Result<(), String> {
+    let vdf_path = steam_path.join("config").join("config.vdf");
+    if !vdf_path.exists() {
+        return Ok(());
+    }
+    let content = fs::read_to_string(&vdf_path).map_err(|e| format!("Failed to read config.vdf: {}", e))?;
+    let mut doc = vdf_text::VdfDocument::parse(&content)?;
+
+    let mut removed = false;
+    for name in account_names {
+        let key = connect_cache_key(name);
+        removed |= doc.remove(&["InstallConfigStore", "Software", "Valve", "Steam", "ConnectCache", &key])?;
+    }
+    if removed {
+        config_backup::write_config(&vdf_path, doc.as_str().as_bytes())?;
+    }
+    Ok(())
+}
+
+fn clear_auto_login_user(account_names: &[&str]) {
+    #[cfg(target_os = "windows")]
+    {
+        use winreg::enums::*;
+        use winreg::RegKey;
+        let hkcu = RegKey::predef(HKEY_CURRENT_USER);
+        if let Ok(key) = hkcu.open_subkey_with_flags("Software\\Valve\\Steam", KEY_READ | KEY_WRITE) {
+            if let Ok(current) = key.get_value::<String, _>("AutoLoginUser") {
+                if account_names.iter().any(|name| name.eq_ignore_ascii_case(&current)) {
+                    let _ = key.set_value("AutoLoginUser", &"");
+                }
+            }
+        }
+    }
+    #[cfg(not(target_os = "windows"))]
+    let _ = account_names;
+}
+
+fn delete_steam_userdata(steam_path: &std::path::Path, steam_id: &str) -> bool {
+    let Ok(steam_id) = SteamId::parse(steam_id) else {
+        return false;
+    };
+    let dir = steam_path.join("userdata").join(steam_id.account_id().to_string());
+    if !dir.is_dir() {
+        return false;
+    }
+    match fs::remove_dir_all(&dir) {
+        Ok(()) => true,
+        Err(e) => {
+            println!("Failed to delete {}: {}", dir.display(), e);
+            false
+        }
+    }
+}
+
+fn close_steam() {
+    #[cfg(target_os = "windows")]
+    {
+        let _ = Command::new("taskkill").args(["/F", "/IM", "steam.exe"]).creation_flags(0x08000000).output();
+        wait_for_process_exit("steam.exe");
+    }
+}
+
+#[tauri::command]
+async fn forget_steam_account(steam_id: String, delete_userdata: bool) -> Result<Vec<ForgottenAccount>, String> {
+    let steam_path = get_steam_path()?;
+    let steam_id = SteamId::parse(&steam_id)?.to_string();
+    // Checked before closing Steam, a typo shouldn't kill a running client
+    if !saved_steam_ids(&steam_path)?.contains(&steam_id) {
+        return Err(format!("No saved Steam account with id {}", steam_id));
+    }
+    close_steam();
+    forget_steam_accounts(&steam_path, std::slice::from_ref(&steam_id), delete_userdata)
+}
+
+// Clears every account from the machine except the pinned ones. With nothing
+// pinned that is every account, so `confirm` must be set explicitly.
+#[tauri::command]
+async fn forget_steam_accounts_except_pinned(delete_userdata: bool, confirm: bool) -> Result<Vec<ForgottenAccount>, String> {
+    let steam_path = get_steam_path()?;
+    let pinned = load_settings().pinned_steam_accounts;
+    if pinned.is_empty() && !confirm {
+        return Err("No Steam account is pinned, this would forget every account".to_string());
+    }
+    let steam_ids: Vec<String> = saved_steam_ids(&steam_path)?
+        .into_iter()
+        .filter(|steam_id| !pinned.contains(steam_id))
+        .collect();
+    if steam_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    close_steam();
+    forget_steam_accounts(&steam_path, &steam_ids, delete_userdata)
+}
+
+// SteamID64s of the accounts in loginusers.vdf
+fn saved_steam_ids(steam_path: &std::path::Path) -> Result<Vec<String>, String> {
+    let content = fs::read_to_string(steam_path.join("config").join("loginusers.vdf"))
+        .map_err(|e| format!("Failed to read loginusers.vdf: {}", e))?;
+    Ok(parse_login_users(&content).into_keys().collect())
+}
+
+#[tauri::command]
+async fn set_steam_account_pinned(steam_id: String, pinned: bool) -> Result<(), String> {
+    let steam_id = SteamId::parse(&steam_id)?.to_string();
+    let mut settings = load_settings();
+    settings.pinned_steam_accounts.retain(|id| id != &steam_id);
+    if pinned {
+        settings.pinned_steam_accounts.push(steam_id);
+    }
+    save_settings(&settings)
+}
+
+// ============================================
+// PROFILE BINDINGS
+// ============================================
+
+#[tauri::command]
+async fn get_profile_bindings() -> Result<HashMap<String, ProfileBinding>, String> {
+    Ok(load_settings().profile_bindings)
+}
+
+#[tauri::command]
+async fn set_profile_binding(app_id: u32, binding: ProfileBinding) -> Result<(), String> {
+    if binding.account_name.is_empty() || binding.account_name == "Unknown" {
+        return Err("Invalid account name".to_string());
+    }
+    let mut settings = load_settings();
+    settings.profile_bindings.insert(app_id.to_string(), binding);
+    save_settings(&settings)
+}
+
+#[tauri::command]
+async fn remove_profile_binding(app_id: u32) -> Result<(), String> {
+    let mut settings = load_settings();
+    if settings.profile_bindings.remove(&app_id.to_string()).is_some() {
+        save_settings(&settings)?;
+    }
+    Ok(())
+}
+
+// Account that last played the game, from its appmanifest's LastOwner
+fn last_user_account(steam_path: &std::path::Path, app_id: u32) -> Option<String> {
+    let last_user = steam_libraries::library_folders(steam_path)
+        .ok()?
+        .into_iter()
+        .find_map(|(path, _)| {
+            let library = steamlocate::Library::from_dir(&path).ok()?;
+            library.app(app_id)?.ok()?.last_user
+        })?;
+    let content = fs::read_to_string(steam_path.join("config").join("loginusers.vdf")).ok()?;
+    parse_login_users(&content).get(&last_user.to_string())?.account_name.clone()
+}
+
+// Arguments left out fall back to the game's profile binding, the account
+// then falls back to whoever played the game last
+#[tauri::command]
+async fn launch_game(app_id: u32, account_name: Option<String>, offline: Option<bool>, launch_option: Option<u32>) -> Result<(), String> {
     let steam_path = get_steam_path()?;
     let steam_exe = steam_path.join("steam.exe");
 
+    let binding = load_settings().profile_bindings.remove(&app_id.to_string());
+    let offline = offline.or(binding.as_ref().map(|b| b.offline)).unwrap_or(false);
+    let launch_option = launch_option.or(binding.as_ref().and_then(|b| b.launch_option));
+    let requested_account = account_name
+        .filter(|account| !account.is_empty() && account != "Unknown")
+        .map(|account| resolve_account_name(&steam_path, &account));
+    let explicit_account = requested_account.is_some();
+    let account_name = requested_account
+        .or_else(|| binding.map(|b| b.account_name))
+        .or_else(|| last_user_account(&steam_path, app_id));
+
     println!("Launching game {} (Offline: {})", app_id, offline);
 
     // 1. Determine & Check Dependencies
@@ -620,31 +1844,23 @@ async fn launch_game(app_id: u32, account_name: Option<String>, offline: bool) -
     if offline {
          // ... (Offline logic as seen in previous file view) ...
          // Redoing abbreviated version for the Replace Block
-         #[cfg(target_os = "windows")]
-         let _ = Command::new("taskkill").args(["/F", "/IM", "steam.exe"]).creation_flags(0x08000000).output();
-         for p in get_launcher_path("Steam") { manage_firewall_rule("Block Steam Exe", &p, true); }
-         for p in get_launcher_path("SteamWebHelper") { manage_firewall_rule("Block Steam WebHelper", &p, true); }
+         close_steam();
+         for (p, rule) in launcher_rule_map("Steam") { manage_firewall_rule(&rule, &p, true); }
          // ... helper VDF patch ...
-         let vdf_path = steam_path.join("config").join("loginusers.vdf");
-         if vdf_path.exists() {
-             if let Ok(content) = fs::read_to_string(&vdf_path) {
-                 let new_content = content.replace("\"WantsOfflineMode\"\t\t\"0\"", "\"WantsOfflineMode\"\t\t\"1\"")
-                                          .replace("\"WantsOfflineMode\" \"0\"", "\"WantsOfflineMode\" \"1\"");
-                 let _ = fs::write(&vdf_path, new_content);
+         match offline_target_steam_id(&steam_path, account_name.as_deref()) {
+             Some(steam_id) => {
+                 if let Err(e) = set_account_offline(&steam_path, &steam_id) {
+                     println!("Offline mode not set: {}", e);
+                 }
              }
+             None => println!("Offline mode not set: no saved login for this account"),
          }
     } else {
-         manage_firewall_rule("Block Steam Exe", "", false);
-         manage_firewall_rule("Block Steam WebHelper", "", false);
+         for (_, rule) in launcher_rule_map("Steam") { manage_firewall_rule(&rule, "", false); }
          // ... cleanup others ...
-         let vdf_path = steam_path.join("config").join("loginusers.vdf");
-          if vdf_path.exists() {
-              if let Ok(content) = fs::read_to_string(&vdf_path) {
-                  let new_content = content.replace("\"WantsOfflineMode\"\t\t\"1\"", "\"WantsOfflineMode\"\t\t\"0\"")
-                                           .replace("\"WantsOfflineMode\" \"1\"", "\"WantsOfflineMode\" \"0\"");
-                  let _ = fs::write(&vdf_path, new_content);
-              }
-          }
+         if let Err(e) = restore_offline_mode(&steam_path) {
+             println!("Offline mode not restored: {}", e);
+         }
     }
 
     // 2. Account Switching & Launch Setup
@@ -652,12 +1868,17 @@ async fn launch_game(app_id: u32, account_name: Option<String>, offline: bool) -
     let mut target_account_id = None;
 
     if let Some(account) = account_name {
-        if !account.is_empty() && account != "Unknown" {
+        let account_id = get_user_account_id(&account);
+        // A bound or last-used account that is already logged in needs no
+        // restart (offline launches killed Steam above, so they always log in)
+        if !explicit_account && !offline && account_id.is_some() && account_id == active_steam_account_id() {
+            println!("{} is already logged in", account);
+        } else {
             // A. Ensure Steam is closed (CLI switch requires Steam restart)
             set_active_steam_user(&account)?;
             
             // B. lookup ID for polling
-            target_account_id = get_user_account_id(&account);
+            target_account_id = account_id;
             explicit_switch = true;
 
             // C. Start Steam with Login First
@@ -689,7 +1910,11 @@ async fn launch_game(app_id: u32, account_name: Option<String>, offline: bool) -
     // 4. Construct Launch Arguments (Stage 2)
     // We run steam.exe again. If it's running, it signals the existing process.
     let mut command = Command::new(&steam_exe);
-    command.arg("-applaunch").arg(app_id.to_string());
+    match launch_option {
+        // A specific config/launch entry, skips Steam's "choose option" dialog
+        Some(option) => command.arg(format!("steam://launch/{}/option{}", app_id, option)),
+        None => command.arg("-applaunch").arg(app_id.to_string()),
+    };
     
     #[cfg(target_os = "windows")]
     command.creation_flags(0x08000000); 
@@ -782,6 +2007,18 @@ pub fn run() {
     .plugin(tauri_plugin_opener::init())
     .plugin(tauri_plugin_dialog::init())
     .plugin(tauri_plugin_fs::init())
+    .setup(|app| {
+        // Fix rules left behind by a Steam update without blocking startup
+        std::thread::spawn(|| {
+            let updated = refresh_steam_helper_rules();
+            if !updated.is_empty() {
+                println!("Updated stale Steam rules: {:?}", updated);
+            }
+        });
+        // Live install/uninstall/login events for the frontend
+        library_watcher::start(app.handle().clone());
+        Ok(())
+    })
     .invoke_handler(tauri::generate_handler![
         close_splash, 
         get_installed_games, 
@@ -790,9 +2027,43 @@ pub fn run() {
         get_launcher_status, 
         open_launcher, 
         get_steam_users, 
+        convert_steam_id,
         switch_steam_account,
         get_launcher_files,
         toggle_file_rule,
+        scan_launcher_executables,
+        inspect_executable,
+        refresh_steam_rules,
+        toggle_game_firewall,
+        get_game_firewall_status,
+        get_app_launch_options,
+        rescan_steam_library,
+        get_steam_libraries,
+        get_all_games,
+        launch_library_game,
+        get_offline_mode_status,
+        get_steam_shortcuts,
+        add_steam_shortcut,
+        remove_steam_shortcut,
+        list_config_backups,
+        restore_config_backup,
+        forget_steam_account,
+        forget_steam_accounts_except_pinned,
+        set_steam_account_pinned,
+        get_profile_bindings,
+        set_profile_binding,
+        remove_profile_binding,
+        save_epic_session,
+        get_epic_sessions,
+        delete_epic_session,
+        switch_epic_account,
+        save_launcher_profile,
+        get_launcher_profiles,
+        delete_launcher_profile,
+        switch_ubisoft_account,
+        switch_ea_account,
+        get_battlenet_accounts,
+        switch_battlenet_account,
         // New launcher detection commands
         auto_detect_launchers,
         save_custom_launcher_path,
@@ -0,0 +1,271 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+use steamlocate::{App, Library};
+
+use crate::config_backup;
+use crate::exe_scanner;
+
+/// Bumped whenever `CachedApp` changes shape, so old caches are discarded.
+const CACHE_VERSION: u32 = 1;
+
+/// The appmanifest fields the game list needs.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CachedApp {
+    pub app_id: u32,
+    pub name: Option<String>,
+    pub install_dir: String,
+    /// Absolute game folder (library root + install_dir)
+    pub install_path: String,
+    pub last_user: Option<u64>,
+    pub size_on_disk: Option<u64>,
+    pub build_id: Option<u64>,
+    /// Seconds since the Unix epoch
+    pub last_updated: Option<u64>,
+    pub state_flags: Option<u64>,
+    pub auto_update_behavior: Option<String>,
+    pub bytes_to_download: Option<u64>,
+    pub bytes_downloaded: Option<u64>,
+}
+
+impl CachedApp {
+    fn from_app(library: &Library, app: &App) -> Self {
+        CachedApp {
+            app_id: app.app_id,
+            name: app.name.clone(),
+            install_dir: app.install_dir.clone(),
+            install_path: library.resolve_app_dir(app).to_string_lossy().to_string(),
+            last_user: app.last_user,
+            size_on_disk: app.size_on_disk,
+            build_id: app.build_id,
+            last_updated: app
+                .last_updated
+                .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                .map(|d| d.as_secs()),
+            state_flags: app.state_flags.as_ref().map(|flags| flags.0),
+            auto_update_behavior: app.auto_update_behavior.as_ref().map(|b| format!("{:?}", b)),
+            bytes_to_download: app.bytes_to_download,
+            bytes_downloaded: app.bytes_downloaded,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct CachedManifest {
+    /// Nanoseconds since the Unix epoch
+    modified: u64,
+    size: u64,
+    app: CachedApp,
+}
+
+/// Main exe guessed for a game folder whose manifest doesn't name one.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct CachedGuess {
+    /// Folder modification time, nanoseconds since the Unix epoch
+    modified: u64,
+    executable: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+struct LibraryCache {
+    version: u32,
+    /// Keyed by absolute appmanifest path
+    manifests: HashMap<String, CachedManifest>,
+    /// Keyed by absolute game folder (EA and Ubisoft games)
+    #[serde(default)]
+    guessed_executables: HashMap<String, CachedGuess>,
+}
+
+pub fn cache_path() -> PathBuf {
+    crate::get_app_data_dir().join("library_cache.json")
+}
+
+/// Drops the cache so the next scan re-parses every manifest.
+pub fn clear() -> Result<(), String> {
+    let path = cache_path();
+    if path.exists() {
+        fs::remove_file(&path).map_err(|e| format!("Failed to delete {}: {}", path.display(), e))?;
+    }
+    Ok(())
+}
+
+/// Apps of every library. Libraries are scanned in parallel and only new or
+/// modified manifests (by mtime and size) are parsed again.
+pub fn scan(libraries: &[Library]) -> Vec<CachedApp> {
+    let cache = load();
+
+    let scanned: Vec<Vec<(String, CachedManifest, bool)>> = std::thread::scope(|scope| {
+        let cache = &cache;
+        let handles: Vec<_> = libraries
+            .iter()
+            .map(|library| scope.spawn(move || scan_library(library, cache)))
+            .collect();
+        handles.into_iter().map(|handle| handle.join().unwrap_or_default()).collect()
+    });
+
+    let mut fresh = LibraryCache {
+        version: CACHE_VERSION,
+        manifests: HashMap::new(),
+        guessed_executables: cache.guessed_executables.clone(),
+    };
+    let mut reparsed = 0;
+    let mut apps = Vec::new();
+    for (key, manifest, changed) in scanned.into_iter().flatten() {
+        if changed {
+            reparsed += 1;
+        }
+        apps.push(manifest.app.clone());
+        fresh.manifests.insert(key, manifest);
+    }
+
+    // Also rewrite when manifests disappeared (uninstalled games)
+    if reparsed > 0 || fresh.manifests.len() != cache.manifests.len() {
+        println!("Library cache: {} of {} manifests parsed", reparsed, fresh.manifests.len());
+        if let Err(e) = save(&fresh) {
+            println!("Failed to save library cache: {}", e);
+        }
+    }
+
+    apps
+}
+
+// (manifest path, entry, re-parsed) for every appmanifest of one library
+fn scan_library(library: &Library, cache: &LibraryCache) -> Vec<(String, CachedManifest, bool)> {
+    let Ok(entries) = fs::read_dir(library.path().join("steamapps")) else {
+        return Vec::new();
+    };
+
+    entries
+        .flatten()
+        .filter_map(|entry| {
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            let app_id: u32 = file_name
+                .strip_prefix("appmanifest_")?
+                .strip_suffix(".acf")?
+                .parse()
+                .ok()?;
+
+            let metadata = entry.metadata().ok()?;
+            let modified = metadata.modified().ok()?.duration_since(UNIX_EPOCH).ok()?.as_nanos() as u64;
+            let size = metadata.len();
+            let key = entry.path().to_string_lossy().to_string();
+
+            if let Some(cached) = cache.manifests.get(&key) {
+                if cached.modified == modified && cached.size == size {
+                    return Some((key, cached.clone(), false));
+                }
+            }
+
+            match library.app(app_id)? {
+                Ok(app) => {
+                    let app = CachedApp::from_app(library, &app);
+                    Some((key, CachedManifest { modified, size, app }, true))
+                }
+                Err(e) => {
+                    println!("Skipping {}: {}", key, e);
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+/// `exe_scanner::guess_main_executable` for several game folders. Each guess
+/// walks the whole folder, so it is cached and only redone when the folder's
+/// modification time changes or the guessed exe is gone.
+pub fn guess_main_executables(game_dirs: &[PathBuf]) -> HashMap<PathBuf, PathBuf> {
+    if game_dirs.is_empty() {
+        return HashMap::new();
+    }
+    let mut cache = load();
+    // Also when there was no cache yet, so the next load accepts it
+    cache.version = CACHE_VERSION;
+    let mut guessed = 0;
+    let mut found = HashMap::new();
+
+    for dir in game_dirs {
+        let key = dir.to_string_lossy().to_string();
+        let modified = folder_modified(dir);
+        let cached = cache.guessed_executables.get(&key).filter(|cached| {
+            cached.modified == modified
+                && cached.executable.as_ref().is_none_or(|exe| Path::new(exe).is_file())
+        });
+
+        let executable = match cached {
+            Some(cached) => cached.executable.clone(),
+            None => {
+                guessed += 1;
+                let executable = exe_scanner::guess_main_executable(dir).map(|exe| exe.to_string_lossy().to_string());
+                cache.guessed_executables.insert(key, CachedGuess { modified, executable: executable.clone() });
+                executable
+            }
+        };
+        if let Some(executable) = executable {
+            found.insert(dir.clone(), PathBuf::from(executable));
+        }
+    }
+
+    if guessed > 0 {
+        println!("Library cache: {} of {} game folders scanned", guessed, game_dirs.len());
+        if let Err(e) = save(&cache) {
+            println!("Failed to save library cache: {}", e);
+        }
+    }
+    found
+}
+
+fn folder_modified(dir: &Path) -> u64 {
+    fs::metadata(dir)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or(0)
+}
+
+fn load() -> LibraryCache {
+    fs::read_to_string(cache_path())
+        .ok()
+        .and_then(|content| serde_json::from_str::<LibraryCache>(&content).ok())
+        .filter(|cache| cache.version == CACHE_VERSION)
+        .unwrap_or_default()
+}
+
+fn save(cache: &LibraryCache) -> Result<(), String> {
+    let path = cache_path();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let json = serde_json::to_string(cache).map_err(|e| e.to_string())?;
+    // A crash mid-write would otherwise leave a truncated cache behind
+    config_backup::atomic_write(&path, json.as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn guesses_are_cached_until_the_folder_changes() {
+        let dir = std::env::temp_dir().join(format!("library_cache_test_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("bin")).unwrap();
+        fs::write(dir.join("game.exe"), vec![0u8; 200]).unwrap();
+        let dirs = vec![dir.clone()];
+
+        let first = guess_main_executables(&dirs);
+        // A larger exe deeper down doesn't touch the folder's own modification time
+        fs::write(dir.join("bin").join("bigger.exe"), vec![0u8; 400]).unwrap();
+        let cached = guess_main_executables(&dirs);
+        // The cached exe is gone, so the folder is scanned again
+        fs::remove_file(dir.join("game.exe")).unwrap();
+        let rescanned = guess_main_executables(&dirs);
+        let _ = fs::remove_dir_all(&dir);
+
+        assert_eq!(first[&dir], dir.join("game.exe"));
+        assert_eq!(cached[&dir], dir.join("game.exe"));
+        assert_eq!(rescanned[&dir], dir.join("bin").join("bigger.exe"));
+    }
+}
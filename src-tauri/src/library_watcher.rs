@@ -0,0 +1,217 @@
+use notify::{Event, RecursiveMode, Watcher};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+use crate::{ea_library, epic_library, steam_libraries};
+
+/// Quiet period before changes are reported. Steam touches a manifest several
+/// times while installing, this turns that into a single event.
+const DEBOUNCE: Duration = Duration::from_millis(1500);
+
+#[derive(Debug, Serialize, Clone)]
+pub struct GameChange {
+    pub launcher_id: String,
+    pub native_id: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct UserChange {
+    pub steam_id: String,
+    pub account_name: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct LibraryChange {
+    pub path: String,
+}
+
+/// Starts the watcher thread. Emits `game-installed`, `game-removed`,
+/// `user-added`, `user-removed`, `library-added` and `library-removed`.
+pub fn start(app: AppHandle) {
+    std::thread::spawn(move || {
+        if let Err(e) = run(&app) {
+            println!("Library watcher stopped: {}", e);
+        }
+    });
+}
+
+fn run(app: &AppHandle) -> Result<(), String> {
+    let (tx, rx) = mpsc::channel::<notify::Result<Event>>();
+    let mut watcher = notify::recommended_watcher(tx).map_err(|e| e.to_string())?;
+
+    let mut watched: HashSet<PathBuf> = HashSet::new();
+    let mut snapshot = Snapshot::take();
+    update_watches(&mut watcher, &mut watched, watch_dirs(&snapshot));
+
+    loop {
+        // Wait for a relevant change, then for things to settle down
+        match rx.recv() {
+            Ok(Ok(event)) if is_relevant(&event) => {}
+            Ok(_) => continue,
+            Err(_) => return Err("Watcher channel closed".to_string()),
+        }
+        while rx.recv_timeout(DEBOUNCE).is_ok() {}
+
+        let next = Snapshot::take();
+        emit_changes(app, &snapshot, &next);
+        if next.libraries != snapshot.libraries {
+            update_watches(&mut watcher, &mut watched, watch_dirs(&next));
+        }
+        snapshot = next;
+    }
+}
+
+fn is_relevant(event: &Event) -> bool {
+    event.paths.iter().any(|path| {
+        let file_name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        (file_name.starts_with("appmanifest_") && file_name.ends_with(".acf"))
+            || file_name == "libraryfolders.vdf"
+            || file_name == "loginusers.vdf"
+            || file_name.ends_with(".item")
+            || path.parent() == Some(Path::new(ea_library::DEFAULT_LIBRARY))
+    })
+}
+
+// Folders whose direct children we care about; missing ones are skipped
+fn watch_dirs(snapshot: &Snapshot) -> HashSet<PathBuf> {
+    let mut dirs: HashSet<PathBuf> = snapshot
+        .libraries
+        .iter()
+        .map(|library| Path::new(library).join("steamapps"))
+        .collect();
+    if let Ok(steam_root) = crate::get_steam_path() {
+        dirs.insert(steam_root.join("steamapps"));
+        dirs.insert(steam_root.join("config"));
+    }
+    dirs.insert(epic_library::manifests_dir());
+    dirs.insert(PathBuf::from(ea_library::DEFAULT_LIBRARY));
+    dirs.retain(|dir| dir.is_dir());
+    dirs
+}
+
+fn update_watches(watcher: &mut impl Watcher, watched: &mut HashSet<PathBuf>, wanted: HashSet<PathBuf>) {
+    for dir in watched.difference(&wanted) {
+        let _ = watcher.unwatch(dir);
+    }
+    for dir in wanted.difference(watched) {
+        if let Err(e) = watcher.watch(dir, RecursiveMode::NonRecursive) {
+            println!("Cannot watch {}: {}", dir.display(), e);
+        }
+    }
+    *watched = wanted;
+}
+
+fn emit_changes(app: &AppHandle, old: &Snapshot, new: &Snapshot) {
+    for (launcher_id, native_id) in new.games.difference(&old.games) {
+        emit(app, "game-installed", GameChange { launcher_id: launcher_id.clone(), native_id: native_id.clone() });
+    }
+    for (launcher_id, native_id) in old.games.difference(&new.games) {
+        emit(app, "game-removed", GameChange { launcher_id: launcher_id.clone(), native_id: native_id.clone() });
+    }
+
+    for (steam_id, account_name) in &new.users {
+        if !old.users.contains_key(steam_id) {
+            emit(app, "user-added", UserChange { steam_id: steam_id.clone(), account_name: account_name.clone() });
+        }
+    }
+    for (steam_id, account_name) in &old.users {
+        if !new.users.contains_key(steam_id) {
+            emit(app, "user-removed", UserChange { steam_id: steam_id.clone(), account_name: account_name.clone() });
+        }
+    }
+
+    for path in new.libraries.difference(&old.libraries) {
+        emit(app, "library-added", LibraryChange { path: path.clone() });
+    }
+    for path in old.libraries.difference(&new.libraries) {
+        emit(app, "library-removed", LibraryChange { path: path.clone() });
+    }
+}
+
+fn emit<T: Serialize + Clone>(app: &AppHandle, event: &str, payload: T) {
+    println!("Library event: {}", event);
+    if let Err(e) = app.emit(event, payload) {
+        println!("Failed to emit {}: {}", event, e);
+    }
+}
+
+// ============================================
+// SNAPSHOT
+// ============================================
+
+/// What the watched files describe at one point in time. Cheap to take: only
+/// directory listings and small manifests, no appmanifest parsing.
+struct Snapshot {
+    /// (launcher id, native id)
+    games: HashSet<(String, String)>,
+    /// SteamID64 -> account name
+    users: HashMap<String, Option<String>>,
+    /// Steam library roots
+    libraries: HashSet<String>,
+}
+
+impl Snapshot {
+    fn take() -> Self {
+        let mut snapshot = Snapshot { games: HashSet::new(), users: HashMap::new(), libraries: HashSet::new() };
+
+        if let Ok(steam_root) = crate::get_steam_path() {
+            if let Ok(folders) = steam_libraries::library_folders(&steam_root) {
+                snapshot.libraries = folders
+                    .into_iter()
+                    .map(|(path, _)| path.to_string_lossy().to_string())
+                    .collect();
+            }
+            for library in &snapshot.libraries {
+                for app_id in steam_app_ids(Path::new(library)) {
+                    snapshot.games.insert(("Steam_ALL".to_string(), app_id.to_string()));
+                }
+            }
+
+            let login_users = steam_root.join("config").join("loginusers.vdf");
+            if let Ok(content) = fs::read_to_string(&login_users) {
+                snapshot.users = crate::parse_login_users(&content)
+                    .into_iter()
+                    .map(|(steam_id, user)| (steam_id, user.account_name))
+                    .collect();
+            }
+        }
+
+        if let Ok(manifests) = epic_library::read_installed_games(&epic_library::manifests_dir()) {
+            for manifest in manifests {
+                snapshot.games.insert(("Epic".to_string(), manifest.app_name));
+            }
+        }
+        for id in ea_library::installed_game_ids() {
+            snapshot.games.insert(("EA".to_string(), id));
+        }
+
+        snapshot
+    }
+}
+
+// App ids from the appmanifest_<id>.acf file names of one library
+fn steam_app_ids(library: &Path) -> Vec<u32> {
+    let Ok(entries) = fs::read_dir(library.join("steamapps")) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .filter_map(|entry| {
+            entry
+                .file_name()
+                .to_string_lossy()
+                .strip_prefix("appmanifest_")?
+                .strip_suffix(".acf")?
+                .parse()
+                .ok()
+        })
+        .collect()
+}
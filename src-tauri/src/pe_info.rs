@@ -0,0 +1,528 @@
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::Path;
+
+const RT_VERSION: u32 = 16;
+const IMAGE_DIRECTORY_ENTRY_RESOURCE: usize = 2;
+const VS_FIXEDFILEINFO_SIGNATURE: u32 = 0xFEEF04BD;
+
+/// Strings from the VERSIONINFO resource of a PE file.
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct PeVersionInfo {
+    pub company_name: Option<String>,
+    pub product_name: Option<String>,
+    pub file_description: Option<String>,
+    pub file_version: Option<String>,
+    pub product_version: Option<String>,
+    pub original_filename: Option<String>,
+}
+
+impl PeVersionInfo {
+    /// Name to show instead of the raw exe name.
+    pub fn friendly_name(&self) -> Option<String> {
+        self.file_description
+            .clone()
+            .or_else(|| self.product_name.clone())
+            .filter(|name| !name.trim().is_empty())
+    }
+
+    /// True if CompanyName contains any of the given vendor names (case-insensitive).
+    pub fn matches_vendor(&self, vendors: &[&str]) -> bool {
+        match &self.company_name {
+            Some(company) => {
+                let company = company.to_lowercase();
+                vendors.iter().any(|v| company.contains(&v.to_lowercase()))
+            }
+            None => false,
+        }
+    }
+
+    /// True if OriginalFilename is missing or names the file (case-insensitive).
+    /// A vendor's exe copied under another launcher's name fails this.
+    pub fn matches_file_name(&self, file_name: &str) -> bool {
+        match &self.original_filename {
+            Some(original) => {
+                let original = original.trim();
+                let original = original.strip_suffix(".mui").unwrap_or(original);
+                original.eq_ignore_ascii_case(file_name)
+            }
+            None => true,
+        }
+    }
+
+    /// Vendor and file name both agree with what the launcher ships.
+    pub fn is_genuine_launcher(&self, vendors: &[&str], file_name: &str) -> bool {
+        self.matches_vendor(vendors) && self.matches_file_name(file_name)
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct PeIdentity {
+    pub path: String,
+    pub size: u64,
+    pub sha256: String,
+    pub version_info: Option<PeVersionInfo>,
+}
+
+/// Reads the version info and SHA-256 hash of an executable.
+pub fn inspect_file(path: &Path) -> Result<PeIdentity, String> {
+    let bytes = fs::read(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+
+    let digest = Sha256::digest(&bytes);
+    let sha256 = digest.iter().map(|b| format!("{:02x}", b)).collect::<String>();
+
+    Ok(PeIdentity {
+        path: path.to_string_lossy().to_string(),
+        size: bytes.len() as u64,
+        sha256,
+        version_info: parse_version_info(&bytes),
+    })
+}
+
+/// Reads only the version info of an executable. `None` if the file is
+/// missing, not a PE image or has no VERSIONINFO resource.
+pub fn read_version_info(path: &Path) -> Option<PeVersionInfo> {
+    let bytes = fs::read(path).ok()?;
+    parse_version_info(&bytes)
+}
+
+/// Parses the VERSIONINFO resource out of raw PE bytes.
+pub fn parse_version_info(bytes: &[u8]) -> Option<PeVersionInfo> {
+    let image = PeImage::parse(bytes)?;
+    let data = image.find_version_resource()?;
+    parse_vs_versioninfo(data)
+}
+
+// ============================================
+// PE HEADERS & RESOURCE DIRECTORY
+// ============================================
+
+struct Section {
+    virtual_address: u32,
+    virtual_size: u32,
+    raw_size: u32,
+    raw_offset: u32,
+}
+
+struct PeImage<'a> {
+    bytes: &'a [u8],
+    sections: Vec<Section>,
+    resource_rva: u32,
+}
+
+impl<'a> PeImage<'a> {
+    fn parse(bytes: &'a [u8]) -> Option<Self> {
+        if bytes.get(0..2)? != b"MZ" {
+            return None;
+        }
+        let pe_offset = read_u32(bytes, 0x3C)? as usize;
+        if bytes.get(pe_offset..pe_offset + 4)? != b"PE\0\0" {
+            return None;
+        }
+
+        let coff = pe_offset + 4;
+        let section_count = read_u16(bytes, coff + 2)? as usize;
+        let optional_size = read_u16(bytes, coff + 16)? as usize;
+        let optional = coff + 20;
+
+        // PE32 and PE32+ differ only in where the data directories start
+        let (dir_count_offset, dirs_offset) = match read_u16(bytes, optional)? {
+            0x10B => (optional + 92, optional + 96),
+            0x20B => (optional + 108, optional + 112),
+            _ => return None,
+        };
+        let dir_count = read_u32(bytes, dir_count_offset)? as usize;
+        if dir_count <= IMAGE_DIRECTORY_ENTRY_RESOURCE {
+            return None;
+        }
+        let resource_rva = read_u32(bytes, dirs_offset + IMAGE_DIRECTORY_ENTRY_RESOURCE * 8)?;
+        if resource_rva == 0 {
+            return None;
+        }
+
+        let section_table = optional + optional_size;
+        let mut sections = Vec::with_capacity(section_count);
+        for i in 0..section_count {
+            let s = section_table + i * 40;
+            sections.push(Section {
+                virtual_size: read_u32(bytes, s + 8)?,
+                virtual_address: read_u32(bytes, s + 12)?,
+                raw_size: read_u32(bytes, s + 16)?,
+                raw_offset: read_u32(bytes, s + 20)?,
+            });
+        }
+
+        Some(Self { bytes, sections, resource_rva })
+    }
+
+    fn rva_to_offset(&self, rva: u32) -> Option<usize> {
+        self.sections.iter().find_map(|s| {
+            let span = s.virtual_size.max(s.raw_size);
+            if rva >= s.virtual_address && rva < s.virtual_address.checked_add(span)? {
+                Some((rva - s.virtual_address).checked_add(s.raw_offset)? as usize)
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Walks type -> name -> language and returns the first RT_VERSION blob.
+    fn find_version_resource(&self) -> Option<&'a [u8]> {
+        let root = self.rva_to_offset(self.resource_rva)?;
+
+        let name_dir = self.find_entry(root, 0, Some(RT_VERSION))?;
+        let lang_dir = self.find_entry(root, subdirectory(name_dir)?, None)?;
+        let data_entry = self.find_entry(root, subdirectory(lang_dir)?, None)?;
+        if data_entry & 0x8000_0000 != 0 {
+            return None;
+        }
+
+        let entry = root + data_entry as usize;
+        let data_rva = read_u32(self.bytes, entry)?;
+        let data_size = read_u32(self.bytes, entry + 4)? as usize;
+        let start = self.rva_to_offset(data_rva)?;
+        self.bytes.get(start..start.checked_add(data_size)?)
+    }
+
+    /// Returns the OffsetToData of the entry with the given id, or of the
+    /// first entry when `id` is `None`. `dir_offset` is relative to `root`.
+    fn find_entry(&self, root: usize, dir_offset: u32, id: Option<u32>) -> Option<u32> {
+        let dir = root + dir_offset as usize;
+        let named = read_u16(self.bytes, dir + 12)? as usize;
+        let ids = read_u16(self.bytes, dir + 14)? as usize;
+
+        for i in 0..named + ids {
+            let entry = dir + 16 + i * 8;
+            let name = read_u32(self.bytes, entry)?;
+            let offset = read_u32(self.bytes, entry + 4)?;
+            match id {
+                None => return Some(offset),
+                Some(wanted) if name & 0x8000_0000 == 0 && name == wanted => return Some(offset),
+                _ => {}
+            }
+        }
+        None
+    }
+}
+
+// High bit of OffsetToData marks a subdirectory
+fn subdirectory(offset: u32) -> Option<u32> {
+    if offset & 0x8000_0000 != 0 {
+        Some(offset & 0x7FFF_FFFF)
+    } else {
+        None
+    }
+}
+
+// ============================================
+// VS_VERSIONINFO
+// ============================================
+
+struct VersionBlock<'a> {
+    key: String,
+    value: &'a [u8],
+    is_text: bool,
+    children: &'a [u8],
+}
+
+/// Parses one `{ wLength, wValueLength, wType, szKey, Value, Children }` block
+/// at the start of `data`. Returns the block and its total (aligned) length.
+fn parse_block(data: &[u8]) -> Option<(VersionBlock<'_>, usize)> {
+    let length = read_u16(data, 0)? as usize;
+    let value_length = read_u16(data, 2)? as usize;
+    let value_type = read_u16(data, 4)?;
+    if length < 6 || length > data.len() {
+        return None;
+    }
+    let block = &data[..length];
+
+    let (key, key_end) = read_utf16z(block, 6)?;
+    let value_start = align4(key_end);
+    // Text values count WCHARs, binary values count bytes
+    let value_bytes = if value_type == 1 { value_length * 2 } else { value_length };
+    let value_end = (value_start + value_bytes).min(length);
+    let value = block.get(value_start.min(length)..value_end).unwrap_or(&[]);
+    let children_start = align4(value_end).min(length);
+
+    Some((
+        VersionBlock {
+            key,
+            value,
+            is_text: value_type == 1,
+            children: &block[children_start..],
+        },
+        align4(length),
+    ))
+}
+
+fn child_blocks(mut data: &[u8]) -> Vec<VersionBlock<'_>> {
+    let mut blocks = Vec::new();
+    while let Some((block, consumed)) = parse_block(data) {
+        blocks.push(block);
+        if consumed >= data.len() {
+            break;
+        }
+        data = &data[consumed..];
+    }
+    blocks
+}
+
+fn parse_vs_versioninfo(data: &[u8]) -> Option<PeVersionInfo> {
+    let (root, _) = parse_block(data)?;
+    if root.key != "VS_VERSION_INFO" {
+        return None;
+    }
+
+    let mut info = PeVersionInfo::default();
+
+    for child in child_blocks(root.children) {
+        if child.key != "StringFileInfo" {
+            continue;
+        }
+        // One StringTable per language; the first one filling a field wins
+        for table in child_blocks(child.children) {
+            for string in child_blocks(table.children) {
+                if !string.is_text {
+                    continue;
+                }
+                let value = decode_utf16(string.value);
+                if value.is_empty() {
+                    continue;
+                }
+                let slot = match string.key.as_str() {
+                    "CompanyName" => &mut info.company_name,
+                    "ProductName" => &mut info.product_name,
+                    "FileDescription" => &mut info.file_description,
+                    "FileVersion" => &mut info.file_version,
+                    "ProductVersion" => &mut info.product_version,
+                    "OriginalFilename" => &mut info.original_filename,
+                    _ => continue,
+                };
+                if slot.is_none() {
+                    *slot = Some(value);
+                }
+            }
+        }
+    }
+
+    // Fall back to the numeric version from VS_FIXEDFILEINFO
+    if info.file_version.is_none() && read_u32(root.value, 0) == Some(VS_FIXEDFILEINFO_SIGNATURE) {
+        if let (Some(ms), Some(ls)) = (read_u32(root.value, 8), read_u32(root.value, 12)) {
+            info.file_version = Some(format!("{}.{}.{}.{}", ms >> 16, ms & 0xFFFF, ls >> 16, ls & 0xFFFF));
+        }
+    }
+
+    Some(info)
+}
+
+// ============================================
+// BYTE HELPERS
+// ============================================
+
+fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    let b = data.get(offset..offset.checked_add(2)?)?;
+    Some(u16::from_le_bytes([b[0], b[1]]))
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    let b = data.get(offset..offset.checked_add(4)?)?;
+    Some(u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+fn align4(offset: usize) -> usize {
+    (offset + 3) & !3
+}
+
+/// Reads a NUL-terminated UTF-16LE string, returning it and the offset after the NUL.
+fn read_utf16z(data: &[u8], offset: usize) -> Option<(String, usize)> {
+    let mut units = Vec::new();
+    let mut pos = offset;
+    loop {
+        let unit = read_u16(data, pos)?;
+        pos += 2;
+        if unit == 0 {
+            break;
+        }
+        units.push(unit);
+    }
+    Some((String::from_utf16_lossy(&units), pos))
+}
+
+fn decode_utf16(data: &[u8]) -> String {
+    let units: Vec<u16> = data
+        .chunks_exact(2)
+        .map(|c| u16::from_le_bytes([c[0], c[1]]))
+        .take_while(|&u| u != 0)
+        .collect();
+    String::from_utf16_lossy(&units).trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECTION_RVA: u32 = 0x1000;
+    const SECTION_OFFSET: usize = 0x200;
+
+    fn utf16z(text: &str) -> Vec<u8> {
+        text.encode_utf16().chain([0]).flat_map(u16::to_le_bytes).collect()
+    }
+
+    fn pad4(buf: &mut Vec<u8>) {
+        buf.resize(align4(buf.len()), 0);
+    }
+
+    fn block(key: &str, value: &[u8], is_text: bool, children: &[Vec<u8>]) -> Vec<u8> {
+        let mut buf = vec![0; 6];
+        buf.extend(utf16z(key));
+        pad4(&mut buf);
+        buf.extend_from_slice(value);
+        for child in children {
+            pad4(&mut buf);
+            buf.extend_from_slice(child);
+        }
+        let value_length = if is_text { value.len() / 2 } else { value.len() };
+        let length = buf.len() as u16;
+        buf[0..2].copy_from_slice(&length.to_le_bytes());
+        buf[2..4].copy_from_slice(&(value_length as u16).to_le_bytes());
+        buf[4..6].copy_from_slice(&(is_text as u16).to_le_bytes());
+        buf
+    }
+
+    fn version_info(strings: &[(&str, &str)]) -> Vec<u8> {
+        let mut fixed = vec![0; 52];
+        fixed[0..4].copy_from_slice(&VS_FIXEDFILEINFO_SIGNATURE.to_le_bytes());
+        fixed[8..12].copy_from_slice(&((2 << 16) | 7u32).to_le_bytes());
+        fixed[12..16].copy_from_slice(&((1 << 16) | 3u32).to_le_bytes());
+
+        let strings: Vec<Vec<u8>> = strings
+            .iter()
+            .map(|(key, value)| block(key, &utf16z(value), true, &[]))
+            .collect();
+        let table = block("040904b0", &[], true, &strings);
+        let string_file_info = block("StringFileInfo", &[], true, &[table]);
+        block("VS_VERSION_INFO", &fixed, false, &[string_file_info])
+    }
+
+    /// Minimal PE32 image with a single `.rsrc` section holding `resource`
+    /// as its RT_VERSION entry.
+    fn pe_image(resource: &[u8]) -> Vec<u8> {
+        let mut rsrc = vec![0u8; 0x58];
+        let mut directory = |at: usize, id: u32, offset: u32| {
+            rsrc[at + 14..at + 16].copy_from_slice(&1u16.to_le_bytes());
+            rsrc[at + 16..at + 20].copy_from_slice(&id.to_le_bytes());
+            rsrc[at + 20..at + 24].copy_from_slice(&offset.to_le_bytes());
+        };
+        directory(0x00, RT_VERSION, 0x8000_0000 | 0x18);
+        directory(0x18, 1, 0x8000_0000 | 0x30);
+        directory(0x30, 0x409, 0x48);
+        rsrc[0x48..0x4C].copy_from_slice(&(SECTION_RVA + 0x58).to_le_bytes());
+        rsrc[0x4C..0x50].copy_from_slice(&(resource.len() as u32).to_le_bytes());
+        rsrc.extend_from_slice(resource);
+
+        let mut image = vec![0u8; SECTION_OFFSET];
+        image[0..2].copy_from_slice(b"MZ");
+        image[0x3C..0x40].copy_from_slice(&0x40u32.to_le_bytes());
+        image[0x40..0x44].copy_from_slice(b"PE\0\0");
+        let coff = 0x44;
+        image[coff + 2..coff + 4].copy_from_slice(&1u16.to_le_bytes());
+        image[coff + 16..coff + 18].copy_from_slice(&224u16.to_le_bytes());
+        let optional = coff + 20;
+        image[optional..optional + 2].copy_from_slice(&0x10Bu16.to_le_bytes());
+        image[optional + 92..optional + 96].copy_from_slice(&16u32.to_le_bytes());
+        let resource_dir = optional + 96 + IMAGE_DIRECTORY_ENTRY_RESOURCE * 8;
+        image[resource_dir..resource_dir + 4].copy_from_slice(&SECTION_RVA.to_le_bytes());
+        image[resource_dir + 4..resource_dir + 8].copy_from_slice(&(rsrc.len() as u32).to_le_bytes());
+
+        let section = optional + 224;
+        image[section..section + 5].copy_from_slice(b".rsrc");
+        image[section + 8..section + 12].copy_from_slice(&(rsrc.len() as u32).to_le_bytes());
+        image[section + 12..section + 16].copy_from_slice(&SECTION_RVA.to_le_bytes());
+        image[section + 16..section + 20].copy_from_slice(&(rsrc.len() as u32).to_le_bytes());
+        image[section + 20..section + 24].copy_from_slice(&(SECTION_OFFSET as u32).to_le_bytes());
+
+        image.extend_from_slice(&rsrc);
+        image
+    }
+
+    fn launcher_image() -> Vec<u8> {
+        pe_image(&version_info(&[
+            ("CompanyName", "Rockstar Games"),
+            ("FileDescription", "Rockstar Games Launcher"),
+            ("ProductName", "Rockstar Games Launcher"),
+            ("FileVersion", "1.0.90.1713"),
+            ("OriginalFilename", "Launcher.exe"),
+        ]))
+    }
+
+    #[test]
+    fn extracts_version_strings() {
+        let info = parse_version_info(&launcher_image()).unwrap();
+        assert_eq!(info.company_name.as_deref(), Some("Rockstar Games"));
+        assert_eq!(info.file_version.as_deref(), Some("1.0.90.1713"));
+        assert_eq!(info.original_filename.as_deref(), Some("Launcher.exe"));
+        assert_eq!(info.product_version, None);
+        assert_eq!(info.friendly_name().as_deref(), Some("Rockstar Games Launcher"));
+    }
+
+    #[test]
+    fn falls_back_to_fixed_file_version() {
+        let info = parse_version_info(&pe_image(&version_info(&[("CompanyName", "Valve")]))).unwrap();
+        assert_eq!(info.file_version.as_deref(), Some("2.7.1.3"));
+    }
+
+    #[test]
+    fn matches_vendor_and_file_name() {
+        let info = parse_version_info(&launcher_image()).unwrap();
+        assert!(info.matches_vendor(&["rockstar"]));
+        assert!(!info.matches_vendor(&["Valve", "Ubisoft"]));
+        assert!(info.is_genuine_launcher(&["Rockstar Games"], "LAUNCHER.EXE"));
+        assert!(!info.is_genuine_launcher(&["Valve"], "Launcher.exe"));
+        // Right vendor, but a different exe of theirs renamed into place
+        assert!(!info.is_genuine_launcher(&["Rockstar Games"], "steam.exe"));
+    }
+
+    #[test]
+    fn missing_original_filename_does_not_fail_identity() {
+        let info = parse_version_info(&pe_image(&version_info(&[("CompanyName", "Valve Corporation")]))).unwrap();
+        assert!(info.is_genuine_launcher(&["Valve"], "steam.exe"));
+    }
+
+    #[test]
+    fn rejects_non_pe_input() {
+        assert!(parse_version_info(b"").is_none());
+        assert!(parse_version_info(b"MZ").is_none());
+        assert!(parse_version_info(b"#!/bin/sh\necho hello\n").is_none());
+
+        let mut image = launcher_image();
+        image[0x40..0x44].copy_from_slice(b"NE\0\0");
+        assert!(parse_version_info(&image).is_none());
+    }
+
+    #[test]
+    fn truncated_images_do_not_panic() {
+        let image = launcher_image();
+        for len in 0..image.len() {
+            assert!(parse_version_info(&image[..len]).is_none(), "parsed with {} bytes", len);
+        }
+    }
+
+    #[test]
+    fn huge_raw_offset_does_not_overflow() {
+        let mut image = launcher_image();
+        let raw_offset = 0x44 + 20 + 224 + 20;
+        image[raw_offset..raw_offset + 4].copy_from_slice(&0xFFFF_FF00u32.to_le_bytes());
+        assert!(parse_version_info(&image).is_none());
+    }
+
+    #[test]
+    fn malformed_blocks_are_skipped() {
+        let mut resource = version_info(&[("CompanyName", "Valve")]);
+        // wLength of VS_VERSION_INFO past the end of the resource
+        resource[0..2].copy_from_slice(&u16::MAX.to_le_bytes());
+        assert!(parse_version_info(&pe_image(&resource)).is_none());
+
+        assert!(parse_version_info(&pe_image(&block("NOT_VERSION_INFO", &[], false, &[]))).is_none());
+    }
+}
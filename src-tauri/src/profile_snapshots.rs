@@ -0,0 +1,390 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::config_backup;
+
+/// Describes where a launcher keeps its logged-in session.
+pub struct SessionSpec {
+    /// Launcher id as used by the detection wizard ("Ubisoft", "EA")
+    pub launcher_id: &'static str,
+    /// Processes to close before the session files can be swapped
+    pub processes: &'static [&'static str],
+    /// (name inside the snapshot, live file or folder)
+    pub entries: Vec<(&'static str, PathBuf)>,
+}
+
+/// Launchers that can be switched by swapping session files.
+pub fn session_spec(launcher_id: &str) -> Option<SessionSpec> {
+    let local_app_data = PathBuf::from(std::env::var("LOCALAPPDATA").unwrap_or_default());
+    let program_data = PathBuf::from(std::env::var("ProgramData").unwrap_or_else(|_| r"C:\ProgramData".to_string()));
+
+    match launcher_id {
+        "Ubisoft" => {
+            let dir = local_app_data.join("Ubisoft Game Launcher");
+            Some(SessionSpec {
+                launcher_id: "Ubisoft",
+                processes: &["upc.exe", "UbisoftConnect.exe", "UplayWebCore.exe"],
+                entries: vec![
+                    ("ConnectSecureStorage.dat", dir.join("ConnectSecureStorage.dat")),
+                    ("user.dat", dir.join("user.dat")),
+                    ("users.dat", dir.join("users.dat")),
+                ],
+            })
+        }
+        "EA" => {
+            let mut entries = vec![
+                // Cookies and local storage of the embedded browser
+                (
+                    "BrowserCache",
+                    local_app_data
+                        .join("Electronic Arts")
+                        .join("EA Desktop")
+                        .join("CEF")
+                        .join("BrowserCache")
+                        .join("EADesktop"),
+                ),
+            ];
+            // Encrypted login state
+            if let Some(is_dir) = ea_login_state_dir(&program_data) {
+                entries.push(("IS", is_dir));
+            }
+            Some(SessionSpec {
+                launcher_id: "EA",
+                processes: &["EADesktop.exe", "EABackgroundService.exe"],
+                entries,
+            })
+        }
+        _ => None,
+    }
+}
+
+// EA keeps its login state in ProgramData\EA Desktop\<hash>\IS. The hash folder
+// isn't documented, so every 64-hex-digit folder is a candidate: the one with an
+// IS folder wins (the newest if several), a logged-out install gets its only one.
+fn ea_login_state_dir(program_data: &Path) -> Option<PathBuf> {
+    let entries = fs::read_dir(program_data.join("EA Desktop")).ok()?;
+    let candidates: Vec<PathBuf> = entries
+        .flatten()
+        .filter(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            name.len() == 64 && name.chars().all(|c| c.is_ascii_hexdigit())
+        })
+        .map(|entry| entry.path().join("IS"))
+        .collect();
+
+    let newest = candidates
+        .iter()
+        .filter_map(|is_dir| Some((fs::metadata(is_dir).ok()?.modified().ok()?, is_dir)))
+        .max_by_key(|(modified, _)| *modified)
+        .map(|(_, is_dir)| is_dir.clone());
+    match candidates.as_slice() {
+        [only] => newest.or_else(|| Some(only.clone())),
+        _ => newest,
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProfileSnapshot {
+    pub launcher_id: String,
+    pub name: String,
+    /// Milliseconds since the Unix epoch
+    pub saved_at: u64,
+    /// Entries that existed when the snapshot was taken
+    pub entries: Vec<String>,
+}
+
+const META_FILE: &str = "snapshot.json";
+
+/// Snapshot `restore` takes of the live session before replacing it, so a
+/// switch can always be undone.
+pub const PREVIOUS_SESSION: &str = "Previous session";
+
+fn snapshots_dir(launcher_id: &str) -> PathBuf {
+    crate::get_app_data_dir().join("profile_snapshots").join(launcher_id)
+}
+
+// Names are trimmed here so saving, switching and deleting agree on the folder
+fn snapshot_dir(launcher_id: &str, name: &str) -> Result<PathBuf, String> {
+    let name = name.trim();
+    let valid = !name.is_empty()
+        && name != "."
+        && name != ".."
+        && !name.contains(['/', '\\', ':', '*', '?', '"', '<', '>', '|']);
+    if !valid {
+        return Err(format!("Invalid profile name: {}", name));
+    }
+    Ok(snapshots_dir(launcher_id).join(name))
+}
+
+/// Copies the launcher's current session into a snapshot named `name`,
+/// replacing an existing one.
+pub fn save(spec: &SessionSpec, name: &str) -> Result<ProfileSnapshot, String> {
+    let name = name.trim();
+    let dir = snapshot_dir(spec.launcher_id, name)?;
+
+    let existing: Vec<&(&str, PathBuf)> = spec.entries.iter().filter(|(_, live)| live.exists()).collect();
+    if existing.is_empty() {
+        return Err(format!("No {} session found, log in first", spec.launcher_id));
+    }
+
+    // Built next to the final folder and swapped in, so a failed copy keeps the old snapshot
+    let staging = dir.with_file_name(format!(".{}.tmp", name));
+    let _ = fs::remove_dir_all(&staging);
+    fs::create_dir_all(&staging).map_err(|e| format!("Failed to create {}: {}", staging.display(), e))?;
+
+    let snapshot = ProfileSnapshot {
+        launcher_id: spec.launcher_id.to_string(),
+        name: name.to_string(),
+        saved_at: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0),
+        entries: existing.iter().map(|(entry, _)| entry.to_string()).collect(),
+    };
+
+    let result = existing
+        .iter()
+        .try_for_each(|(entry, live)| copy_entry(live, &staging.join(entry)))
+        .and_then(|_| {
+            let json = serde_json::to_string_pretty(&snapshot).map_err(|e| e.to_string())?;
+            fs::write(staging.join(META_FILE), json).map_err(|e| e.to_string())
+        });
+    if let Err(e) = result {
+        let _ = fs::remove_dir_all(&staging);
+        return Err(e);
+    }
+
+    if dir.exists() {
+        fs::remove_dir_all(&dir).map_err(|e| format!("Failed to replace profile {}: {}", name, e))?;
+    }
+    fs::rename(&staging, &dir).map_err(|e| format!("Failed to save profile {}: {}", name, e))?;
+    Ok(snapshot)
+}
+
+/// Saved snapshots of one launcher, by name.
+pub fn list(launcher_id: &str) -> Vec<ProfileSnapshot> {
+    let Ok(entries) = fs::read_dir(snapshots_dir(launcher_id)) else {
+        return Vec::new();
+    };
+    let mut snapshots: Vec<ProfileSnapshot> = entries
+        .flatten()
+        // Staging folders of an unfinished save or restore
+        .filter(|entry| !entry.file_name().to_string_lossy().starts_with('.'))
+        .filter_map(|entry| fs::read_to_string(entry.path().join(META_FILE)).ok())
+        .filter_map(|content| serde_json::from_str(&content).ok())
+        .collect();
+    snapshots.sort_by_key(|snapshot| snapshot.name.to_lowercase());
+    snapshots
+}
+
+pub fn delete(launcher_id: &str, name: &str) -> Result<(), String> {
+    let dir = snapshot_dir(launcher_id, name)?;
+    if !dir.join(META_FILE).exists() {
+        return Err(format!("Profile not found: {}", name));
+    }
+    fs::remove_dir_all(&dir).map_err(|e| format!("Failed to delete profile {}: {}", name, e))
+}
+
+/// Replaces the live session with a snapshot. Entries the snapshot doesn't
+/// have are removed, so no part of the previous login is left behind.
+/// The live session is saved as `PREVIOUS_SESSION` first; restoring that
+/// snapshot swaps the two. The launcher must be closed.
+pub fn restore(spec: &SessionSpec, name: &str) -> Result<ProfileSnapshot, String> {
+    let name = name.trim();
+    let dir = snapshot_dir(spec.launcher_id, name)?;
+    let content = fs::read_to_string(dir.join(META_FILE)).map_err(|_| format!("Profile not found: {}", name))?;
+    let snapshot: ProfileSnapshot =
+        serde_json::from_str(&content).map_err(|e| format!("Invalid profile {}: {}", name, e))?;
+
+    let unknown = snapshot
+        .entries
+        .iter()
+        .find(|saved| !spec.entries.iter().any(|(entry, _)| entry == saved));
+    if let Some(entry) = unknown {
+        return Err(format!("Can't restore {} of profile {}: not found on this machine", entry, name));
+    }
+
+    // The previous session snapshot is about to be overwritten, move it aside first
+    let source = if name == PREVIOUS_SESSION {
+        let aside = dir.with_file_name(format!(".{}.restore", name));
+        let _ = fs::remove_dir_all(&aside);
+        fs::rename(&dir, &aside).map_err(|e| format!("Failed to prepare profile {}: {}", name, e))?;
+        aside
+    } else {
+        dir.clone()
+    };
+
+    if spec.entries.iter().any(|(_, live)| live.exists()) {
+        if let Err(e) = save(spec, PREVIOUS_SESSION) {
+            if source != dir {
+                let _ = fs::rename(&source, &dir);
+            }
+            return Err(format!("Failed to back up the current session: {}", e));
+        }
+    }
+
+    replace_live_entries(spec, &source)?;
+    if source != dir {
+        let _ = fs::remove_dir_all(&source);
+    }
+    Ok(snapshot)
+}
+
+fn replace_live_entries(spec: &SessionSpec, dir: &Path) -> Result<(), String> {
+    for (entry, live) in &spec.entries {
+        let saved = dir.join(entry);
+        if saved.is_dir() {
+            replace_dir(&saved, live)?;
+        } else if saved.is_file() {
+            let data = fs::read(&saved).map_err(|e| format!("Failed to read {}: {}", saved.display(), e))?;
+            if let Some(parent) = live.parent() {
+                fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+            }
+            config_backup::write_config(live, &data)?;
+        } else if live.is_dir() {
+            fs::remove_dir_all(live).map_err(|e| format!("Failed to remove {}: {}", live.display(), e))?;
+        } else if live.is_file() {
+            fs::remove_file(live).map_err(|e| format!("Failed to remove {}: {}", live.display(), e))?;
+        }
+    }
+    Ok(())
+}
+
+fn copy_entry(from: &Path, to: &Path) -> Result<(), String> {
+    if from.is_dir() {
+        copy_dir(from, to)
+    } else {
+        fs::copy(from, to)
+            .map(|_| ())
+            .map_err(|e| format!("Failed to copy {}: {}", from.display(), e))
+    }
+}
+
+fn copy_dir(from: &Path, to: &Path) -> Result<(), String> {
+    fs::create_dir_all(to).map_err(|e| format!("Failed to create {}: {}", to.display(), e))?;
+    let entries = fs::read_dir(from).map_err(|e| format!("Failed to read {}: {}", from.display(), e))?;
+    for entry in entries.flatten() {
+        copy_entry(&entry.path(), &to.join(entry.file_name()))?;
+    }
+    Ok(())
+}
+
+// Copies next to the live folder first, then swaps it in
+fn replace_dir(saved: &Path, live: &Path) -> Result<(), String> {
+    let file_name = live
+        .file_name()
+        .ok_or_else(|| format!("Invalid path: {}", live.display()))?
+        .to_string_lossy();
+    let staging = live.with_file_name(format!(".{}.tmp", file_name));
+    let _ = fs::remove_dir_all(&staging);
+
+    if let Err(e) = copy_dir(saved, &staging) {
+        let _ = fs::remove_dir_all(&staging);
+        return Err(e);
+    }
+    if live.exists() {
+        fs::remove_dir_all(live).map_err(|e| format!("Failed to remove {}: {}", live.display(), e))?;
+    }
+    fs::rename(&staging, live).map_err(|e| format!("Failed to replace {}: {}", live.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A session of one file and one folder below a temp dir
+    fn test_spec(launcher_id: &'static str) -> SessionSpec {
+        let live = std::env::temp_dir().join(format!("profile_snapshots_test_{}_{}", launcher_id, std::process::id()));
+        let _ = fs::remove_dir_all(&live);
+        let _ = fs::remove_dir_all(snapshots_dir(launcher_id));
+        SessionSpec {
+            launcher_id,
+            processes: &[],
+            entries: vec![("user.dat", live.join("user.dat")), ("Cache", live.join("Cache"))],
+        }
+    }
+
+    fn log_in(spec: &SessionSpec, user: &str) {
+        let (_, file) = &spec.entries[0];
+        let (_, folder) = &spec.entries[1];
+        fs::create_dir_all(folder).unwrap();
+        fs::write(file, user).unwrap();
+        fs::write(folder.join("cookie"), user).unwrap();
+    }
+
+    fn live_user(spec: &SessionSpec) -> (String, String) {
+        let (_, file) = &spec.entries[0];
+        let (_, folder) = &spec.entries[1];
+        (fs::read_to_string(file).unwrap(), fs::read_to_string(folder.join("cookie")).unwrap())
+    }
+
+    fn cleanup(spec: &SessionSpec) {
+        let _ = fs::remove_dir_all(spec.entries[0].1.parent().unwrap());
+        let _ = fs::remove_dir_all(snapshots_dir(spec.launcher_id));
+    }
+
+    #[test]
+    fn restore_keeps_the_replaced_session() {
+        let spec = test_spec("TestRestore");
+        log_in(&spec, "alice");
+        save(&spec, "Alice").unwrap();
+        log_in(&spec, "bob");
+
+        restore(&spec, " Alice ").unwrap();
+        let after_switch = live_user(&spec);
+        let names: Vec<String> = list(spec.launcher_id).into_iter().map(|s| s.name).collect();
+
+        // Switching back to the previous session swaps the two again
+        restore(&spec, PREVIOUS_SESSION).unwrap();
+        let after_undo = live_user(&spec);
+        let previous = fs::read_to_string(snapshot_dir(spec.launcher_id, PREVIOUS_SESSION).unwrap().join("user.dat"));
+        let names_after_undo: Vec<String> = list(spec.launcher_id).into_iter().map(|s| s.name).collect();
+        cleanup(&spec);
+
+        assert_eq!(after_switch, ("alice".to_string(), "alice".to_string()));
+        assert_eq!(names, ["Alice", PREVIOUS_SESSION]);
+        assert_eq!(after_undo, ("bob".to_string(), "bob".to_string()));
+        assert_eq!(previous.unwrap(), "alice");
+        assert_eq!(names_after_undo, ["Alice", PREVIOUS_SESSION]);
+    }
+
+    #[test]
+    fn restore_removes_entries_missing_from_the_snapshot() {
+        let spec = test_spec("TestMissing");
+        fs::create_dir_all(spec.entries[0].1.parent().unwrap()).unwrap();
+        fs::write(&spec.entries[0].1, "alice").unwrap();
+        save(&spec, "Alice").unwrap();
+        log_in(&spec, "bob");
+
+        restore(&spec, "Alice").unwrap();
+        let file = fs::read_to_string(&spec.entries[0].1);
+        let folder_left = spec.entries[1].1.exists();
+        cleanup(&spec);
+
+        assert_eq!(file.unwrap(), "alice");
+        assert!(!folder_left);
+    }
+
+    #[test]
+    fn ea_login_state_is_found_in_any_hash_folder() {
+        let program_data = std::env::temp_dir().join(format!("profile_snapshots_test_ea_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&program_data);
+        let ea = program_data.join("EA Desktop");
+        let hash = "0123456789abcdef".repeat(4);
+        fs::create_dir_all(ea.join(&hash)).unwrap();
+        fs::create_dir_all(ea.join("Logs")).unwrap();
+
+        // Logged out: the only hash folder
+        let logged_out = ea_login_state_dir(&program_data);
+        // A second install folder, only this one is logged in
+        let other = "fedcba9876543210".repeat(4);
+        fs::create_dir_all(ea.join(&other).join("IS")).unwrap();
+        let logged_in = ea_login_state_dir(&program_data);
+        let _ = fs::remove_dir_all(&program_data);
+
+        assert_eq!(logged_out, Some(ea.join(&hash).join("IS")));
+        assert_eq!(logged_in, Some(ea.join(&other).join("IS")));
+    }
+}
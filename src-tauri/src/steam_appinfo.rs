@@ -0,0 +1,435 @@
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+use crate::vdf_binary::{BinaryMap, BinaryValue, Reader};
+
+// appinfo.vdf magic numbers; the low byte is the format version
+const MAGIC_V27: u32 = 0x0756_4427;
+const MAGIC_V28: u32 = 0x0756_4428;
+const MAGIC_V29: u32 = 0x0756_4429;
+
+/// One `config/launch` entry of an app.
+#[derive(Debug, Serialize, Clone)]
+pub struct LaunchOption {
+    /// Key under `config/launch`, as used by `steam://launch/<appid>/option<N>`
+    pub index: u32,
+    /// Relative to the install folder
+    pub executable: String,
+    pub arguments: String,
+    pub description: Option<String>,
+    /// "default", "option1", "none", ...
+    pub launch_type: Option<String>,
+    /// Comma separated ("windows", "macos,linux"); empty means any OS
+    pub oslist: Option<String>,
+}
+
+impl LaunchOption {
+    pub fn runs_on_windows(&self) -> bool {
+        match &self.oslist {
+            Some(oslist) if !oslist.trim().is_empty() => {
+                oslist.split(',').any(|os| os.trim().eq_ignore_ascii_case("windows"))
+            }
+            _ => true,
+        }
+    }
+}
+
+pub fn appinfo_path(steam_root: &Path) -> PathBuf {
+    steam_root.join("appcache").join("appinfo.vdf")
+}
+
+/// Launch options of one app; empty when the app has none.
+pub fn read_launch_options(steam_root: &Path, app_id: u32) -> Result<Vec<LaunchOption>, String> {
+    let mut reader = AppInfoReader::open(&appinfo_path(steam_root))?;
+    Ok(reader
+        .find(app_id)?
+        .map(|appinfo| launch_options(&appinfo))
+        .unwrap_or_default())
+}
+
+/// Launch options of several apps in a single pass over the file.
+pub fn read_all_launch_options(
+    steam_root: &Path,
+    app_ids: &HashSet<u32>,
+) -> Result<HashMap<u32, Vec<LaunchOption>>, String> {
+    let mut reader = AppInfoReader::open(&appinfo_path(steam_root))?;
+    let mut options = HashMap::new();
+    reader.for_each_app(app_ids, |app_id, appinfo| {
+        options.insert(app_id, launch_options(&appinfo));
+    })?;
+    Ok(options)
+}
+
+/// `config/launch` entries of a parsed app record.
+pub fn launch_options(appinfo: &BinaryMap) -> Vec<LaunchOption> {
+    let Some(launch) = appinfo
+        .get_map("appinfo")
+        .and_then(|info| info.get_map("config"))
+        .and_then(|config| config.get_map("launch"))
+    else {
+        return Vec::new();
+    };
+
+    let mut options: Vec<LaunchOption> = launch
+        .entries
+        .iter()
+        .filter_map(|(key, value)| {
+            let BinaryValue::Map(entry) = value else { return None };
+            let executable = entry.get_str("executable")?.trim().to_string();
+            if executable.is_empty() {
+                return None;
+            }
+            Some(LaunchOption {
+                index: key.parse().ok()?,
+                executable,
+                arguments: entry.get_str("arguments").unwrap_or_default().to_string(),
+                description: entry.get_str("description").map(str::to_string),
+                launch_type: entry.get_str("type").map(str::to_string),
+                oslist: entry
+                    .get_map("config")
+                    .and_then(|config| config.get_str("oslist"))
+                    .map(str::to_string),
+            })
+        })
+        .collect();
+    options.sort_by_key(|option| option.index);
+    options
+}
+
+/// Windows launch executables that exist under `install_dir`, relative to it,
+/// in launch option order without duplicates.
+pub fn windows_executables(options: &[LaunchOption], install_dir: &Path) -> Vec<String> {
+    let mut executables: Vec<String> = Vec::new();
+    for option in options.iter().filter(|option| option.runs_on_windows()) {
+        let relative = option
+            .executable
+            .replace('/', "\\")
+            .trim_start_matches(".\\")
+            .trim_start_matches('\\')
+            .to_string();
+        if !relative.to_lowercase().ends_with(".exe") || !install_dir.join(&relative).is_file() {
+            continue;
+        }
+        if !executables.iter().any(|exe| exe.eq_ignore_ascii_case(&relative)) {
+            executables.push(relative);
+        }
+    }
+    executables
+}
+
+// ============================================
+// READER
+// ============================================
+
+/// Walks appinfo.vdf record by record, only parsing the apps asked for.
+/// The file is often hundreds of MB, so the rest is skipped by size.
+pub struct AppInfoReader {
+    reader: BufReader<File>,
+    version: u32,
+    key_table: Vec<String>,
+}
+
+impl AppInfoReader {
+    pub fn open(path: &Path) -> Result<Self, String> {
+        let file = File::open(path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+        let mut reader = BufReader::new(file);
+
+        let magic = read_u32(&mut reader)?;
+        let _universe = read_u32(&mut reader)?;
+        let version = match magic {
+            MAGIC_V27 => 27,
+            MAGIC_V28 => 28,
+            MAGIC_V29 => 29,
+            other => return Err(format!("Unsupported appinfo.vdf format: 0x{:08x}", other)),
+        };
+
+        // v29 moved every key into a string table at the end of the file
+        let mut key_table = Vec::new();
+        if version >= 29 {
+            let table_offset = read_u64(&mut reader)?;
+            let records_start = reader.stream_position().map_err(|e| e.to_string())?;
+
+            reader.seek(SeekFrom::Start(table_offset)).map_err(|e| e.to_string())?;
+            let count = read_u32(&mut reader)?;
+            for _ in 0..count {
+                let mut bytes = Vec::new();
+                reader.read_until(0, &mut bytes).map_err(|e| e.to_string())?;
+                if bytes.pop() != Some(0) {
+                    return Err("Truncated appinfo.vdf string table".to_string());
+                }
+                key_table.push(String::from_utf8_lossy(&bytes).to_string());
+            }
+            reader.seek(SeekFrom::Start(records_start)).map_err(|e| e.to_string())?;
+        }
+
+        Ok(Self { reader, version, key_table })
+    }
+
+    /// Parsed record of `app_id`, or `None` when Steam has no info for it.
+    pub fn find(&mut self, app_id: u32) -> Result<Option<BinaryMap>, String> {
+        while let Some((id, size)) = self.next_header()? {
+            if id == app_id {
+                return self.read_record(size).map(Some);
+            }
+            self.skip(size)?;
+        }
+        Ok(None)
+    }
+
+    /// Calls `f` for every app in `app_ids` that has a record.
+    pub fn for_each_app(
+        &mut self,
+        app_ids: &HashSet<u32>,
+        mut f: impl FnMut(u32, BinaryMap),
+    ) -> Result<(), String> {
+        let mut remaining = app_ids.len();
+        while remaining > 0 {
+            let Some((id, size)) = self.next_header()? else { break };
+            if app_ids.contains(&id) {
+                f(id, self.read_record(size)?);
+                remaining -= 1;
+            } else {
+                self.skip(size)?;
+            }
+        }
+        Ok(())
+    }
+
+    // (app id, record size); None at the terminating app id 0
+    fn next_header(&mut self) -> Result<Option<(u32, u32)>, String> {
+        let app_id = read_u32(&mut self.reader)?;
+        if app_id == 0 {
+            return Ok(None);
+        }
+        let size = read_u32(&mut self.reader)?;
+        Ok(Some((app_id, size)))
+    }
+
+    fn skip(&mut self, size: u32) -> Result<(), String> {
+        self.reader.seek_relative(size as i64).map_err(|e| e.to_string())
+    }
+
+    fn read_record(&mut self, size: u32) -> Result<BinaryMap, String> {
+        let mut record = vec![0u8; size as usize];
+        self.reader
+            .read_exact(&mut record)
+            .map_err(|e| format!("Truncated appinfo.vdf record: {}", e))?;
+
+        // infoState, lastUpdated, picsToken, text SHA-1, changeNumber
+        // and, since v28, the SHA-1 of the binary data
+        let header_len = if self.version >= 28 { 60 } else { 40 };
+        let data = record
+            .get(header_len..)
+            .ok_or_else(|| "appinfo.vdf record shorter than its header".to_string())?;
+
+        let mut reader = if self.version >= 29 {
+            Reader::with_key_table(data, &self.key_table)
+        } else {
+            Reader::new(data)
+        };
+        reader.read_map()
+    }
+}
+
+fn read_u32(reader: &mut impl Read) -> Result<u32, String> {
+    let mut bytes = [0u8; 4];
+    reader
+        .read_exact(&mut bytes)
+        .map_err(|e| format!("Unexpected end of appinfo.vdf: {}", e))?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn read_u64(reader: &mut impl Read) -> Result<u64, String> {
+    let mut bytes = [0u8; 8];
+    reader
+        .read_exact(&mut bytes)
+        .map_err(|e| format!("Unexpected end of appinfo.vdf: {}", e))?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vdf_binary;
+    use std::fs;
+
+    fn string(value: &str) -> BinaryValue {
+        BinaryValue::String(value.to_string())
+    }
+
+    fn map(entries: Vec<(&str, BinaryValue)>) -> BinaryMap {
+        BinaryMap {
+            entries: entries.into_iter().map(|(k, v)| (k.to_string(), v)).collect(),
+        }
+    }
+
+    fn launch_entry(executable: &str, oslist: Option<&str>) -> BinaryValue {
+        let mut entry = map(vec![("executable", string(executable)), ("type", string("default"))]);
+        if let Some(oslist) = oslist {
+            entry.set("config", BinaryValue::Map(map(vec![("oslist", string(oslist))])));
+        }
+        BinaryValue::Map(entry)
+    }
+
+    // What an app record parses to: appinfo -> config -> launch -> "<index>"
+    fn app_record(launch: Vec<(&str, BinaryValue)>) -> BinaryMap {
+        let config = map(vec![("launch", BinaryValue::Map(map(launch)))]);
+        let appinfo = map(vec![("appid", BinaryValue::Int32(220)), ("config", BinaryValue::Map(config))]);
+        map(vec![("appinfo", BinaryValue::Map(appinfo))])
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("steam_appinfo_test_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn reads_launch_options_in_index_order() {
+        let record = app_record(vec![
+            ("1", launch_entry("bin/tool.exe", Some("windows"))),
+            ("0", launch_entry(" hl2.exe ", None)),
+            ("2", launch_entry("hl2.sh", Some("linux"))),
+            ("3", launch_entry("", None)),
+            ("x", launch_entry("ignored.exe", None)),
+        ]);
+        let options = launch_options(&record);
+        let summary: Vec<(u32, &str)> = options.iter().map(|o| (o.index, o.executable.as_str())).collect();
+        assert_eq!(summary, vec![(0, "hl2.exe"), (1, "bin/tool.exe"), (2, "hl2.sh")]);
+        assert_eq!(options[0].launch_type.as_deref(), Some("default"));
+        assert!(options[0].runs_on_windows());
+        assert!(options[1].runs_on_windows());
+        assert!(!options[2].runs_on_windows());
+
+        assert!(launch_options(&BinaryMap::new()).is_empty());
+    }
+
+    #[test]
+    fn oslist_matches_windows_anywhere_in_the_list() {
+        let option = |oslist: &str| LaunchOption {
+            index: 0,
+            executable: "game.exe".to_string(),
+            arguments: String::new(),
+            description: None,
+            launch_type: None,
+            oslist: Some(oslist.to_string()),
+        };
+        assert!(option("macos, Windows").runs_on_windows());
+        assert!(option(" ").runs_on_windows());
+        assert!(!option("macos,linux").runs_on_windows());
+    }
+
+    #[test]
+    fn windows_executables_are_existing_unique_exes() {
+        let dir = temp_dir("executables");
+        fs::write(dir.join("game.exe"), b"").unwrap();
+        fs::write(dir.join("tool.exe"), b"").unwrap();
+
+        let options = launch_options(&app_record(vec![
+            ("0", launch_entry("./game.exe", None)),
+            ("1", launch_entry("/tool.exe", Some("windows"))),
+            ("2", launch_entry("game.exe", None)),
+            ("3", launch_entry("missing.exe", None)),
+            ("4", launch_entry("tool.exe", Some("linux"))),
+            ("5", launch_entry("launch.bat", None)),
+        ]));
+        let executables = windows_executables(&options, &dir);
+        let _ = fs::remove_dir_all(&dir);
+
+        assert_eq!(executables, vec!["game.exe".to_string(), "tool.exe".to_string()]);
+    }
+
+    fn record_bytes(app_id: u32, data: &[u8], header_len: usize) -> Vec<u8> {
+        let mut out = app_id.to_le_bytes().to_vec();
+        out.extend_from_slice(&((header_len + data.len()) as u32).to_le_bytes());
+        out.resize(out.len() + header_len, 0);
+        out.extend_from_slice(data);
+        out
+    }
+
+    #[test]
+    fn reads_v28_records() {
+        let dir = temp_dir("v28");
+        let path = dir.join("appinfo.vdf");
+        let mut file = MAGIC_V28.to_le_bytes().to_vec();
+        file.extend_from_slice(&1u32.to_le_bytes());
+        file.extend(record_bytes(10, &vdf_binary::write(&app_record(vec![])), 60));
+        file.extend(record_bytes(220, &vdf_binary::write(&app_record(vec![("0", launch_entry("hl2.exe", None))])), 60));
+        file.extend_from_slice(&0u32.to_le_bytes());
+        fs::write(&path, file).unwrap();
+
+        let found = AppInfoReader::open(&path).unwrap().find(220).unwrap();
+        let missing = AppInfoReader::open(&path).unwrap().find(440).unwrap();
+        let mut all = HashMap::new();
+        AppInfoReader::open(&path)
+            .unwrap()
+            .for_each_app(&HashSet::from([10, 220, 440]), |id, record| {
+                all.insert(id, launch_options(&record).len());
+            })
+            .unwrap();
+        let _ = fs::remove_dir_all(&dir);
+
+        assert_eq!(launch_options(&found.unwrap())[0].executable, "hl2.exe");
+        assert!(missing.is_none());
+        assert_eq!(all, HashMap::from([(10, 0), (220, 1)]));
+    }
+
+    #[test]
+    fn reads_v29_records_through_the_key_table() {
+        let keys = ["appinfo", "config", "launch", "0", "executable"];
+        let key = |name: &str| (keys.iter().position(|k| *k == name).unwrap() as u32).to_le_bytes();
+        let mut data = Vec::new();
+        for name in ["appinfo", "config", "launch", "0"] {
+            data.push(0x00);
+            data.extend_from_slice(&key(name));
+        }
+        data.push(0x01);
+        data.extend_from_slice(&key("executable"));
+        data.extend_from_slice(b"hl2.exe\x00");
+        data.extend_from_slice(&[0x08; 5]);
+
+        let mut file = MAGIC_V29.to_le_bytes().to_vec();
+        file.extend_from_slice(&1u32.to_le_bytes());
+        let records = record_bytes(220, &data, 60);
+        let table_offset = (file.len() + 8 + records.len() + 4) as u64;
+        file.extend_from_slice(&table_offset.to_le_bytes());
+        file.extend(records);
+        file.extend_from_slice(&0u32.to_le_bytes());
+        file.extend_from_slice(&(keys.len() as u32).to_le_bytes());
+        for name in keys {
+            file.extend_from_slice(name.as_bytes());
+            file.push(0);
+        }
+
+        let dir = temp_dir("v29");
+        fs::create_dir_all(dir.join("appcache")).unwrap();
+        fs::write(appinfo_path(&dir), file).unwrap();
+        let options = read_launch_options(&dir, 220);
+        let _ = fs::remove_dir_all(&dir);
+
+        assert_eq!(options.unwrap()[0].executable, "hl2.exe");
+    }
+
+    #[test]
+    fn rejects_unknown_and_truncated_files() {
+        let dir = temp_dir("invalid");
+        let path = dir.join("appinfo.vdf");
+
+        fs::write(&path, 0x0756_4426u32.to_le_bytes()).unwrap();
+        let unknown = AppInfoReader::open(&path).err();
+
+        let mut truncated = MAGIC_V28.to_le_bytes().to_vec();
+        truncated.extend_from_slice(&1u32.to_le_bytes());
+        truncated.extend_from_slice(&record_bytes(220, b"\x01name\x00x\x00\x08", 60)[..30]);
+        fs::write(&path, truncated).unwrap();
+        let cut = AppInfoReader::open(&path).unwrap().find(220);
+        let _ = fs::remove_dir_all(&dir);
+
+        assert!(unknown.is_some());
+        assert!(cut.is_err());
+    }
+}
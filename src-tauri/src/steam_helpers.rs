@@ -0,0 +1,53 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Steam helper processes that talk to the network, each with its own rule.
+/// (rule name, path relative to the Steam root)
+const FIXED_HELPERS: &[(&str, &str)] = &[
+    ("Block Steam Service", r"bin\steamservice.exe"),
+    ("Block Steam Overlay", "GameOverlayUI.exe"),
+    ("Block Steam Overlay64", "GameOverlayUI64.exe"),
+];
+
+pub const WEB_HELPER_RULE: &str = "Block Steam WebHelper";
+
+#[derive(Debug, Clone)]
+pub struct SteamHelper {
+    pub rule_name: &'static str,
+    pub path: PathBuf,
+}
+
+/// Finds the network helpers of the Steam install at `steam_root`.
+/// Only helpers that exist on disk are returned.
+pub fn find_steam_helpers(steam_root: &Path) -> Vec<SteamHelper> {
+    let mut helpers = Vec::new();
+
+    if let Some(path) = find_web_helper(steam_root) {
+        helpers.push(SteamHelper { rule_name: WEB_HELPER_RULE, path });
+    }
+
+    for (rule_name, relative) in FIXED_HELPERS {
+        let path = steam_root.join(relative);
+        if path.is_file() {
+            helpers.push(SteamHelper { rule_name, path });
+        }
+    }
+
+    helpers
+}
+
+/// Looks for `steamwebhelper.exe` in every `bin\cef\*` folder.
+///
+/// Valve renames the CEF folder between updates (cef.win7, cef.win7x64, ...),
+/// and old folders are sometimes left behind. The most recently modified
+/// helper is the one Steam currently runs.
+pub fn find_web_helper(steam_root: &Path) -> Option<PathBuf> {
+    let cef_dir = steam_root.join("bin").join("cef");
+    let entries = fs::read_dir(&cef_dir).ok()?;
+
+    entries
+        .flatten()
+        .map(|entry| entry.path().join("steamwebhelper.exe"))
+        .filter(|path| path.is_file())
+        .max_by_key(|path| fs::metadata(path).and_then(|m| m.modified()).ok())
+}
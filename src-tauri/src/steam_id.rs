@@ -0,0 +1,183 @@
+use std::fmt;
+
+/// Universe of every regular Steam account.
+const UNIVERSE_PUBLIC: u64 = 1;
+
+/// Account type of user accounts ("U" in SteamID3).
+const TYPE_INDIVIDUAL: u64 = 1;
+
+/// Instance used by user accounts on the desktop client.
+const INSTANCE_DESKTOP: u64 = 1;
+
+/// A Steam user account id.
+///
+/// Parses every form admins tend to copy around:
+/// - SteamID64: `76561197960287930`
+/// - SteamID3: `[U:1:22202]` (brackets optional)
+/// - legacy SteamID: `STEAM_0:0:11101` (`STEAM_1:` too)
+/// - bare account id, as used for `userdata` folders: `22202`
+///
+/// Only individual accounts in the public universe are accepted; group and
+/// game server ids are rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SteamId(u64);
+
+impl SteamId {
+    pub fn from_account_id(account_id: u32) -> Self {
+        SteamId((UNIVERSE_PUBLIC << 56) | (TYPE_INDIVIDUAL << 52) | (INSTANCE_DESKTOP << 32) | account_id as u64)
+    }
+
+    pub fn from_steam_id64(id64: u64) -> Result<Self, String> {
+        let universe = id64 >> 56;
+        let account_type = (id64 >> 52) & 0xF;
+        if universe != UNIVERSE_PUBLIC {
+            return Err(format!("SteamID {} is not in the public universe", id64));
+        }
+        if account_type != TYPE_INDIVIDUAL {
+            return Err(format!("SteamID {} is not a user account", id64));
+        }
+        if id64 as u32 == 0 {
+            return Err(format!("SteamID {} has no account id", id64));
+        }
+        Ok(SteamId(id64))
+    }
+
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let text = input.trim();
+        let invalid = || format!("Not a Steam ID: {}", input);
+
+        if let Some(rest) = strip_prefix_ignore_case(text, "STEAM_") {
+            return parse_legacy(rest).ok_or_else(invalid);
+        }
+
+        let bare = text.strip_prefix('[').and_then(|t| t.strip_suffix(']')).unwrap_or(text);
+        if let Some(rest) = strip_prefix_ignore_case(bare, "U:") {
+            return parse_steam_id3(rest).ok_or_else(invalid);
+        }
+
+        let number: u64 = text.parse().map_err(|_| invalid())?;
+        match u32::try_from(number) {
+            Ok(0) => Err(invalid()),
+            Ok(account_id) => Ok(SteamId::from_account_id(account_id)),
+            Err(_) => SteamId::from_steam_id64(number),
+        }
+    }
+
+    pub fn steam_id64(self) -> u64 {
+        self.0
+    }
+
+    /// Lower 32 bits, also the name of the account's `userdata` folder
+    pub fn account_id(self) -> u32 {
+        self.0 as u32
+    }
+
+    /// `[U:1:<account id>]`
+    pub fn steam_id3(self) -> String {
+        format!("[U:{}:{}]", self.0 >> 56, self.account_id())
+    }
+
+    /// `STEAM_0:<account id & 1>:<account id >> 1>`
+    pub fn steam_id2(self) -> String {
+        let account_id = self.account_id();
+        format!("STEAM_0:{}:{}", account_id & 1, account_id >> 1)
+    }
+}
+
+/// Formats as SteamID64, the form `loginusers.vdf` is keyed by.
+impl fmt::Display for SteamId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+fn strip_prefix_ignore_case<'a>(text: &'a str, prefix: &str) -> Option<&'a str> {
+    let head = text.get(..prefix.len())?;
+    head.eq_ignore_ascii_case(prefix).then(|| &text[prefix.len()..])
+}
+
+// "X:Y:Z" after "STEAM_": universe X (0 means public in old games), account id Z * 2 + Y
+fn parse_legacy(rest: &str) -> Option<SteamId> {
+    let mut parts = rest.split(':');
+    let universe: u64 = parts.next()?.parse().ok()?;
+    let low_bit: u32 = parts.next()?.parse().ok()?;
+    let high: u32 = parts.next()?.parse().ok()?;
+    if parts.next().is_some() || universe > UNIVERSE_PUBLIC || low_bit > 1 {
+        return None;
+    }
+    let account_id = high.checked_mul(2)?.checked_add(low_bit)?;
+    (account_id != 0).then(|| SteamId::from_account_id(account_id))
+}
+
+// "1:N" after "U:"
+fn parse_steam_id3(rest: &str) -> Option<SteamId> {
+    let (universe, account_id) = rest.split_once(':')?;
+    let universe: u64 = universe.parse().ok()?;
+    let account_id: u32 = account_id.parse().ok()?;
+    (universe == UNIVERSE_PUBLIC && account_id != 0).then(|| SteamId::from_account_id(account_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GABEN: u64 = 76561197960287930;
+
+    #[test]
+    fn parses_every_form_to_the_same_id() {
+        let inputs = [
+            "76561197960287930",
+            "[U:1:22202]",
+            "U:1:22202",
+            "u:1:22202",
+            "STEAM_0:0:11101",
+            "STEAM_1:0:11101",
+            "steam_0:0:11101",
+            " 22202 ",
+        ];
+        for input in inputs {
+            assert_eq!(SteamId::parse(input).map(SteamId::steam_id64), Ok(GABEN), "{}", input);
+        }
+    }
+
+    #[test]
+    fn formats_every_form() {
+        let id = SteamId::from_account_id(22202);
+        assert_eq!(id.steam_id64(), GABEN);
+        assert_eq!(id.account_id(), 22202);
+        assert_eq!(id.steam_id3(), "[U:1:22202]");
+        assert_eq!(id.steam_id2(), "STEAM_0:0:11101");
+        assert_eq!(id.to_string(), "76561197960287930");
+    }
+
+    #[test]
+    fn odd_account_ids_keep_the_low_bit() {
+        let id = SteamId::parse("STEAM_0:1:11101").unwrap();
+        assert_eq!(id.account_id(), 22203);
+        assert_eq!(id.steam_id2(), "STEAM_0:1:11101");
+        assert_eq!(SteamId::parse(&id.steam_id3()), Ok(id));
+    }
+
+    #[test]
+    fn rejects_non_user_ids() {
+        // Group and game server SteamID64s
+        assert!(SteamId::parse("103582791429521412").is_err());
+        assert!(SteamId::parse("90071996842377216").is_err());
+        assert!(SteamId::parse("[G:1:4]").is_err());
+        assert!(SteamId::parse("[U:2:22202]").is_err());
+        assert!(SteamId::parse("STEAM_2:0:11101").is_err());
+        assert!(SteamId::parse("STEAM_0:2:11101").is_err());
+    }
+
+    #[test]
+    fn rejects_empty_and_out_of_range_ids() {
+        for input in ["", "0", "[U:1:0]", "STEAM_0:0:0", "abc", "-22202", "STEAM_0:0:11101:1"] {
+            assert!(SteamId::parse(input).is_err(), "{}", input);
+        }
+        // Account id does not fit in 32 bits
+        assert!(SteamId::parse("4294967296").is_err());
+        assert!(SteamId::parse("[U:1:4294967296]").is_err());
+        assert!(SteamId::parse("STEAM_0:1:2147483648").is_err());
+        assert!(SteamId::parse("18446744073709551616").is_err());
+    }
+}
@@ -0,0 +1,130 @@
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use steamlocate::Library;
+
+use crate::vdf_text;
+
+/// One entry of `libraryfolders.vdf` with its disk and content summary.
+#[derive(Debug, Serialize, Clone)]
+pub struct SteamLibraryInfo {
+    pub path: String,
+    pub label: String,
+    /// The folder and its `steamapps` directory exist
+    pub reachable: bool,
+    pub disk_total: Option<u64>,
+    pub disk_free: Option<u64>,
+    pub game_count: usize,
+    /// Sum of SizeOnDisk over the library's appmanifests
+    pub total_size: u64,
+    /// appmanifest_*.acf files that couldn't be parsed
+    pub failed_manifests: usize,
+    /// Why the library couldn't be read, if it couldn't
+    pub error: Option<String>,
+}
+
+pub fn libraryfolders_path(steam_root: &Path) -> PathBuf {
+    steam_root.join("steamapps").join("libraryfolders.vdf")
+}
+
+/// Every library Steam knows about, including unreachable ones.
+pub fn read_libraries(steam_root: &Path) -> Result<Vec<SteamLibraryInfo>, String> {
+    Ok(library_folders(steam_root)?
+        .into_iter()
+        .map(|(path, label)| library_info(&path, label))
+        .collect())
+}
+
+/// (path, label) pairs; the Steam folder itself when the file is missing
+pub fn library_folders(steam_root: &Path) -> Result<Vec<(PathBuf, String)>, String> {
+    let vdf_path = libraryfolders_path(steam_root);
+    if !vdf_path.exists() {
+        return Ok(vec![(steam_root.to_path_buf(), String::new())]);
+    }
+
+    let content = fs::read_to_string(&vdf_path).map_err(|e| format!("Failed to read {}: {}", vdf_path.display(), e))?;
+    let root = vdf_text::parse(&content)?;
+    let folders = root
+        .get_map("libraryfolders")
+        .ok_or_else(|| "libraryfolders.vdf has no \"libraryfolders\" key".to_string())?;
+
+    Ok(folders
+        .maps()
+        .filter_map(|(_, folder)| {
+            let path = folder.get_str("path")?;
+            Some((PathBuf::from(path), folder.get_str("label").unwrap_or_default().to_string()))
+        })
+        .collect())
+}
+
+fn library_info(path: &Path, label: String) -> SteamLibraryInfo {
+    let (disk_total, disk_free) = match disk_space(path) {
+        Some((total, free)) => (Some(total), Some(free)),
+        None => (None, None),
+    };
+    let mut info = SteamLibraryInfo {
+        path: path.to_string_lossy().to_string(),
+        label,
+        reachable: path.join("steamapps").is_dir(),
+        disk_total,
+        disk_free,
+        game_count: 0,
+        total_size: 0,
+        failed_manifests: 0,
+        error: None,
+    };
+
+    if !info.reachable {
+        info.error = Some(format!("{} is not reachable", info.path));
+        return info;
+    }
+
+    match Library::from_dir(path) {
+        Ok(library) => {
+            for app in library.apps() {
+                match app {
+                    Ok(app) => {
+                        info.game_count += 1;
+                        info.total_size += app.size_on_disk.unwrap_or(0);
+                    }
+                    Err(_) => info.failed_manifests += 1,
+                }
+            }
+        }
+        Err(e) => info.error = Some(e.to_string()),
+    }
+    info
+}
+
+#[cfg(target_os = "windows")]
+#[link(name = "kernel32")]
+extern "system" {
+    fn GetDiskFreeSpaceExW(
+        directory: *const u16,
+        free_bytes_available: *mut u64,
+        total_bytes: *mut u64,
+        total_free_bytes: *mut u64,
+    ) -> i32;
+}
+
+/// (total, free for the current user) bytes of the volume holding `path`
+pub fn disk_space(path: &Path) -> Option<(u64, u64)> {
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::ffi::OsStrExt;
+
+        let wide: Vec<u16> = path.as_os_str().encode_wide().chain(std::iter::once(0)).collect();
+        let (mut free, mut total, mut total_free) = (0u64, 0u64, 0u64);
+        let ok = unsafe { GetDiskFreeSpaceExW(wide.as_ptr(), &mut free, &mut total, &mut total_free) };
+        if ok != 0 {
+            Some((total, free))
+        } else {
+            None
+        }
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        let _ = path;
+        None
+    }
+}
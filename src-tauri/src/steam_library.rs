@@ -0,0 +1,195 @@
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::process::Command;
+
+#[cfg(target_os = "windows")]
+use std::os::windows::process::CommandExt;
+
+use crate::games::{Game, GameExtras, GameLibraryProvider};
+use crate::steam_appinfo;
+use crate::SteamGame;
+
+/// Decoded `StateFlags` bits of an appmanifest (Steam's EAppState).
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+pub enum AppState {
+    Uninstalled,
+    UpdateRequired,
+    FullyInstalled,
+    Encrypted,
+    Locked,
+    FilesMissing,
+    AppRunning,
+    FilesCorrupt,
+    UpdateRunning,
+    UpdatePaused,
+    UpdateStarted,
+    Uninstalling,
+    BackupRunning,
+    Reconfiguring,
+    Validating,
+    AddingFiles,
+    Preallocating,
+    Downloading,
+    Staging,
+    Committing,
+    UpdateStopping,
+}
+
+const STATE_BITS: &[(u64, AppState)] = &[
+    (1 << 0, AppState::Uninstalled),
+    (1 << 1, AppState::UpdateRequired),
+    (1 << 2, AppState::FullyInstalled),
+    (1 << 3, AppState::Encrypted),
+    (1 << 4, AppState::Locked),
+    (1 << 5, AppState::FilesMissing),
+    (1 << 6, AppState::AppRunning),
+    (1 << 7, AppState::FilesCorrupt),
+    (1 << 8, AppState::UpdateRunning),
+    (1 << 9, AppState::UpdatePaused),
+    (1 << 10, AppState::UpdateStarted),
+    (1 << 11, AppState::Uninstalling),
+    (1 << 12, AppState::BackupRunning),
+    (1 << 16, AppState::Reconfiguring),
+    (1 << 17, AppState::Validating),
+    (1 << 18, AppState::AddingFiles),
+    (1 << 19, AppState::Preallocating),
+    (1 << 20, AppState::Downloading),
+    (1 << 21, AppState::Staging),
+    (1 << 22, AppState::Committing),
+    (1 << 23, AppState::UpdateStopping),
+];
+
+pub fn decode_state_flags(bits: u64) -> Vec<AppState> {
+    STATE_BITS
+        .iter()
+        .filter(|(bit, _)| bits & bit != 0)
+        .map(|(_, state)| *state)
+        .collect()
+}
+
+/// A game that can't be played right now without Steam updating it first.
+/// Missing or undecodable `StateFlags` say nothing about the game and don't count.
+pub fn needs_update(state_flags: Option<u64>) -> bool {
+    let states = state_flags.map(decode_state_flags).unwrap_or_default();
+    if states.is_empty() {
+        return false;
+    }
+    !states.contains(&AppState::FullyInstalled)
+        || states.iter().any(|state| {
+            matches!(
+                state,
+                AppState::UpdateRequired
+                    | AppState::UpdateRunning
+                    | AppState::UpdatePaused
+                    | AppState::UpdateStarted
+                    | AppState::Downloading
+                    | AppState::Staging
+                    | AppState::Committing
+                    | AppState::FilesMissing
+                    | AppState::FilesCorrupt
+            )
+        })
+}
+
+/// Steam provider; the library scan itself stays in `collect_steam_games`
+/// so `get_installed_games` keeps returning the Steam-specific shape.
+pub struct SteamLibrary;
+
+impl GameLibraryProvider for SteamLibrary {
+    fn launcher_id(&self) -> &'static str {
+        "Steam_ALL"
+    }
+
+    fn games(&self) -> Result<Vec<Game>, String> {
+        let steam_games = crate::collect_steam_games()?;
+
+        // Executables come from the launch configs in appinfo.vdf
+        let app_ids: HashSet<u32> = steam_games.iter().map(|game| game.id).collect();
+        let launch_options = crate::get_steam_path()
+            .and_then(|root| steam_appinfo::read_all_launch_options(&root, &app_ids))
+            .unwrap_or_else(|e| {
+                println!("appinfo.vdf skipped: {}", e);
+                HashMap::new()
+            });
+
+        Ok(steam_games
+            .into_iter()
+            .map(|steam_game| {
+                let install_dir = Path::new(&steam_game.install_path).to_path_buf();
+                let executables: Vec<String> = launch_options
+                    .get(&steam_game.id)
+                    .map(|options| steam_appinfo::windows_executables(options, &install_dir))
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|relative| install_dir.join(relative).to_string_lossy().to_string())
+                    .collect();
+
+                let mut game = Game::from(steam_game);
+                game.executables = executables;
+                game
+            })
+            .collect())
+    }
+
+    fn launch(&self, native_id: &str) -> Result<(), String> {
+        let steam_exe = crate::get_steam_path()?.join("steam.exe");
+        let mut command = Command::new(&steam_exe);
+        command.arg("-applaunch").arg(native_id);
+        #[cfg(target_os = "windows")]
+        command.creation_flags(0x08000000);
+        command.spawn().map_err(|e| format!("Failed to launch game: {}", e))?;
+        Ok(())
+    }
+}
+
+impl From<SteamGame> for Game {
+    fn from(game: SteamGame) -> Self {
+        Game {
+            launcher_id: "Steam_ALL".to_string(),
+            native_id: game.id.to_string(),
+            name: game.name,
+            install_path: game.install_path,
+            executables: Vec::new(),
+            size_on_disk: game.size_on_disk,
+            last_played: game.last_played,
+            extras: GameExtras::Steam {
+                owner_name: game.owner_name,
+                account_name: game.account_name,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_or_unknown_flags_are_not_updates() {
+        assert!(!needs_update(None));
+        assert!(!needs_update(Some(0)));
+        // Only bits Steam doesn't define
+        assert!(!needs_update(Some(1 << 13)));
+    }
+
+    #[test]
+    fn fully_installed_is_playable() {
+        assert_eq!(decode_state_flags(4), vec![AppState::FullyInstalled]);
+        assert!(!needs_update(Some(4)));
+        // Running the game doesn't make it stale
+        assert!(!needs_update(Some(4 | 64)));
+    }
+
+    #[test]
+    fn pending_or_broken_installs_need_updates() {
+        // UpdateRequired
+        assert!(needs_update(Some(6)));
+        // Installed, files missing
+        assert!(needs_update(Some(4 | 32)));
+        // Downloading without FullyInstalled
+        assert!(needs_update(Some(1 << 20)));
+        // Uninstalled
+        assert!(needs_update(Some(1)));
+    }
+}
@@ -0,0 +1,151 @@
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::UNIX_EPOCH;
+
+use crate::steam_shortcuts;
+use crate::vdf_text;
+
+/// One account's activity for one app, from its `localconfig.vdf`.
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct AppActivity {
+    pub playtime_minutes: u64,
+    pub playtime_2weeks_minutes: u64,
+    /// Seconds since the Unix epoch
+    pub last_played: Option<u64>,
+    pub launch_options: Option<String>,
+}
+
+/// Playtime of a game on one account, as shown in the game list.
+#[derive(Debug, Serialize, Clone)]
+pub struct AccountPlaytime {
+    pub account_id: u32,
+    pub account_name: Option<String>,
+    pub persona_name: Option<String>,
+    #[serde(flatten)]
+    pub activity: AppActivity,
+}
+
+/// Parsed `localconfig.vdf` with the mtime (nanoseconds) and size it was read at.
+struct CachedConfig {
+    modified: u64,
+    size: u64,
+    apps: HashMap<u32, AppActivity>,
+}
+
+/// Keyed by absolute file path. The files are large and change rarely, so the
+/// game list only parses them again when they were modified.
+static CACHE: Mutex<BTreeMap<PathBuf, CachedConfig>> = Mutex::new(BTreeMap::new());
+
+pub fn localconfig_path(steam_root: &Path, account_id: u32) -> PathBuf {
+    steam_root
+        .join("userdata")
+        .join(account_id.to_string())
+        .join("config")
+        .join("localconfig.vdf")
+}
+
+/// Activity of every account with a userdata folder, keyed by account id and
+/// then app id. Accounts whose file can't be read are logged and skipped.
+/// Only files new or modified (by mtime and size) since the last call are parsed.
+pub fn read_all(steam_root: &Path) -> HashMap<u32, HashMap<u32, AppActivity>> {
+    let mut accounts = HashMap::new();
+    for account_id in steam_shortcuts::userdata_accounts(steam_root) {
+        match read_account_cached(steam_root, account_id) {
+            Ok(apps) => {
+                accounts.insert(account_id, apps);
+            }
+            Err(e) => println!("Skipping localconfig of {}: {}", account_id, e),
+        }
+    }
+    accounts
+}
+
+fn read_account_cached(steam_root: &Path, account_id: u32) -> Result<HashMap<u32, AppActivity>, String> {
+    let path = localconfig_path(steam_root, account_id);
+    let metadata = fs::metadata(&path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let modified = metadata
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or(0);
+    let size = metadata.len();
+
+    let mut cache = CACHE.lock().map_err(|e| e.to_string())?;
+    if let Some(cached) = cache.get(&path) {
+        if cached.modified == modified && cached.size == size {
+            return Ok(cached.apps.clone());
+        }
+    }
+
+    let apps = read_account(steam_root, account_id)?;
+    cache.insert(path, CachedConfig { modified, size, apps: apps.clone() });
+    Ok(apps)
+}
+
+/// App activity of one account, keyed by app id. Apps never played on this
+/// account are left out.
+pub fn read_account(steam_root: &Path, account_id: u32) -> Result<HashMap<u32, AppActivity>, String> {
+    let path = localconfig_path(steam_root, account_id);
+    let content = fs::read_to_string(&path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let root = vdf_text::parse(&content)?;
+
+    let Some(apps) = root.get_path(&["UserLocalConfigStore", "Software", "Valve", "Steam", "apps"]) else {
+        return Ok(HashMap::new());
+    };
+
+    let number = |value: Option<&str>| value.and_then(|v| v.trim().parse::<u64>().ok()).unwrap_or(0);
+
+    Ok(apps
+        .maps()
+        .filter_map(|(app_id, app)| {
+            let activity = AppActivity {
+                playtime_minutes: number(app.get_str("Playtime")),
+                playtime_2weeks_minutes: number(app.get_str("Playtime2wks")),
+                last_played: Some(number(app.get_str("LastPlayed"))).filter(|&t| t > 0),
+                launch_options: app
+                    .get_str("LaunchOptions")
+                    .map(str::to_string)
+                    .filter(|options| !options.is_empty()),
+            };
+            if activity.playtime_minutes == 0 && activity.last_played.is_none() {
+                return None;
+            }
+            Some((app_id.parse::<u32>().ok()?, activity))
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_localconfig(steam_root: &Path, account_id: u32, playtime: u64) {
+        let path = localconfig_path(steam_root, account_id);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        let content = format!(
+            "\"UserLocalConfigStore\"\n{{\n\t\"Software\"\n\t{{\n\t\t\"Valve\"\n\t\t{{\n\t\t\t\"Steam\"\n\t\t\t{{\n\t\t\t\t\"apps\"\n\t\t\t\t{{\n\t\t\t\t\t\"440\"\n\t\t\t\t\t{{\n\t\t\t\t\t\t\"Playtime\"\t\t\"{}\"\n\t\t\t\t\t}}\n\t\t\t\t}}\n\t\t\t}}\n\t\t}}\n\t}}\n}}\n",
+            playtime
+        );
+        fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn modified_files_are_parsed_again() {
+        let root = std::env::temp_dir().join(format!("steam_localconfig_test_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+
+        write_localconfig(&root, 12345, 5);
+        let first = read_all(&root);
+        // Different length, so the size alone marks the file as changed
+        write_localconfig(&root, 12345, 1234);
+        let second = read_all(&root);
+        let _ = fs::remove_dir_all(&root);
+
+        assert_eq!(first[&12345][&440].playtime_minutes, 5);
+        assert_eq!(second[&12345][&440].playtime_minutes, 1234);
+    }
+}
@@ -0,0 +1,333 @@
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::config_backup;
+use crate::games::{self, Game, GameExtras, GameLibraryProvider};
+use crate::vdf_binary::{self, BinaryMap, BinaryValue};
+
+/// A non-Steam game added to one account's library.
+#[derive(Debug, Serialize, Clone)]
+pub struct SteamShortcut {
+    /// Owning account (userdata folder name)
+    pub account_id: u32,
+    /// Filled in from loginusers.vdf by the caller
+    pub account_name: Option<String>,
+    pub app_id: u32,
+    /// 64-bit id used by `steam://rungameid/<game_id>`
+    pub game_id: u64,
+    pub app_name: String,
+    pub exe: String,
+    pub start_dir: String,
+    pub launch_options: String,
+}
+
+/// Non-Steam shortcuts as their own library, launched through Steam so the
+/// overlay and controller config still apply.
+pub struct SteamShortcutLibrary;
+
+impl GameLibraryProvider for SteamShortcutLibrary {
+    fn launcher_id(&self) -> &'static str {
+        "Steam_Shortcut"
+    }
+
+    fn games(&self) -> Result<Vec<Game>, String> {
+        let steam_root = crate::get_steam_path()?;
+        Ok(list_all(&steam_root).into_iter().map(Game::from).collect())
+    }
+
+    fn launch(&self, native_id: &str) -> Result<(), String> {
+        games::open_launch_uri(&format!("steam://rungameid/{}", native_id))
+    }
+}
+
+impl From<SteamShortcut> for Game {
+    fn from(shortcut: SteamShortcut) -> Self {
+        let exe = shortcut.exe.trim_matches('"').to_string();
+        Game {
+            launcher_id: "Steam_Shortcut".to_string(),
+            native_id: shortcut.game_id.to_string(),
+            name: shortcut.app_name,
+            install_path: shortcut.start_dir.trim_matches('"').to_string(),
+            executables: vec![exe],
+            size_on_disk: None,
+            last_played: None,
+            extras: GameExtras::SteamShortcut {
+                account_id: shortcut.account_id,
+                launch_options: shortcut.launch_options,
+            },
+        }
+    }
+}
+
+pub struct NewShortcut<'a> {
+    pub app_name: &'a str,
+    pub exe: &'a str,
+    pub start_dir: Option<&'a str>,
+    pub launch_options: Option<&'a str>,
+}
+
+/// Shortcut app id as Steam computes it: CRC32 of the quoted exe followed by
+/// the name, with the high bit set.
+pub fn shortcut_app_id(exe: &str, app_name: &str) -> u32 {
+    let mut hasher = crc32fast::Hasher::new();
+    hasher.update(exe.as_bytes());
+    hasher.update(app_name.as_bytes());
+    hasher.finalize() | 0x8000_0000
+}
+
+pub fn shortcut_game_id(app_id: u32) -> u64 {
+    ((app_id as u64) << 32) | 0x0200_0000
+}
+
+pub fn shortcuts_path(steam_root: &Path, account_id: u32) -> PathBuf {
+    steam_root
+        .join("userdata")
+        .join(account_id.to_string())
+        .join("config")
+        .join("shortcuts.vdf")
+}
+
+/// Account ids that have a userdata folder
+pub fn userdata_accounts(steam_root: &Path) -> Vec<u32> {
+    let Ok(entries) = fs::read_dir(steam_root.join("userdata")) else {
+        return Vec::new();
+    };
+    let mut accounts: Vec<u32> = entries
+        .flatten()
+        .filter_map(|entry| entry.file_name().to_string_lossy().parse::<u32>().ok())
+        .filter(|&id| id != 0)
+        .collect();
+    accounts.sort_unstable();
+    accounts
+}
+
+/// Shortcuts of every account on this machine. Unreadable files are logged
+/// and skipped so one broken account doesn't hide the others.
+pub fn list_all(steam_root: &Path) -> Vec<SteamShortcut> {
+    let mut shortcuts = Vec::new();
+    for account_id in userdata_accounts(steam_root) {
+        match read_account(steam_root, account_id) {
+            Ok(list) => shortcuts.extend(list),
+            Err(e) => println!("Skipping shortcuts of {}: {}", account_id, e),
+        }
+    }
+    shortcuts
+}
+
+pub fn read_account(steam_root: &Path, account_id: u32) -> Result<Vec<SteamShortcut>, String> {
+    let root = load(&shortcuts_path(steam_root, account_id))?;
+    let Some(list) = root.get_map("shortcuts") else {
+        return Ok(Vec::new());
+    };
+
+    Ok(list
+        .entries
+        .iter()
+        .filter_map(|(_, value)| match value {
+            BinaryValue::Map(entry) => Some(to_shortcut(account_id, entry)),
+            _ => None,
+        })
+        .collect())
+}
+
+/// Checks that `add` would succeed, without writing anything. Lets the caller
+/// refuse before closing Steam.
+pub fn validate(steam_root: &Path, account_id: u32, new: &NewShortcut) -> Result<(), String> {
+    let root = load(&shortcuts_path(steam_root, account_id))?;
+    prepare(root.get_map("shortcuts"), new).map(|_| ())
+}
+
+/// Adds a shortcut to the account's `shortcuts.vdf`.
+/// Steam must be closed, it rewrites the file from memory on exit.
+pub fn add(steam_root: &Path, account_id: u32, new: NewShortcut) -> Result<SteamShortcut, String> {
+    let path = shortcuts_path(steam_root, account_id);
+    let mut root = load(&path)?;
+    let (exe, start_dir, app_id) = prepare(root.get_map("shortcuts"), &new)?;
+
+    if root.get_map("shortcuts").is_none() {
+        root.set("shortcuts", BinaryValue::Map(BinaryMap::new()));
+    }
+    let list = root.get_map_mut("shortcuts").ok_or("Invalid shortcuts.vdf")?;
+
+    let mut entry = BinaryMap::new();
+    entry.set("appid", BinaryValue::Int32(app_id as i32));
+    entry.set("AppName", BinaryValue::String(new.app_name.to_string()));
+    entry.set("Exe", BinaryValue::String(exe));
+    entry.set("StartDir", BinaryValue::String(start_dir));
+    entry.set("icon", BinaryValue::String(String::new()));
+    entry.set("ShortcutPath", BinaryValue::String(String::new()));
+    entry.set("LaunchOptions", BinaryValue::String(new.launch_options.unwrap_or_default().to_string()));
+    entry.set("IsHidden", BinaryValue::Int32(0));
+    entry.set("AllowDesktopConfig", BinaryValue::Int32(1));
+    entry.set("AllowOverlay", BinaryValue::Int32(1));
+    entry.set("OpenVR", BinaryValue::Int32(0));
+    entry.set("Devkit", BinaryValue::Int32(0));
+    entry.set("DevkitGameID", BinaryValue::String(String::new()));
+    entry.set("DevkitOverrideAppID", BinaryValue::Int32(0));
+    entry.set("LastPlayTime", BinaryValue::Int32(0));
+    entry.set("FlatpakAppID", BinaryValue::String(String::new()));
+    entry.set("tags", BinaryValue::Map(BinaryMap::new()));
+
+    let shortcut = to_shortcut(account_id, &entry);
+    list.entries.push((list.entries.len().to_string(), BinaryValue::Map(entry)));
+
+    save(&path, &root)?;
+    Ok(shortcut)
+}
+
+// Quoted exe, quoted start dir and app id of a new shortcut, or why it can't be added
+fn prepare(list: Option<&BinaryMap>, new: &NewShortcut) -> Result<(String, String, u32), String> {
+    let exe = quote(new.exe);
+    let start_dir = match new.start_dir {
+        Some(dir) if !dir.trim_matches('"').is_empty() => dir.trim_matches('"').to_string(),
+        // Default to the exe's folder, like Steam's "Add a Non-Steam Game"
+        _ => Path::new(new.exe.trim_matches('"'))
+            .parent()
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or_default(),
+    };
+    if start_dir.is_empty() {
+        return Err(format!("No start folder for {}, use the full path of the executable", new.app_name));
+    }
+    let app_id = shortcut_app_id(&exe, new.app_name);
+
+    let already_added = list.is_some_and(|list| {
+        list.entries.iter().any(|(_, value)| match value {
+            BinaryValue::Map(entry) => entry_app_id(entry) == app_id,
+            _ => false,
+        })
+    });
+    if already_added {
+        return Err(format!("{} is already in this account's library", new.app_name));
+    }
+
+    Ok((exe, quote(&start_dir), app_id))
+}
+
+/// Removes a shortcut and renumbers the remaining entries ("0", "1", ...).
+pub fn remove(steam_root: &Path, account_id: u32, app_id: u32) -> Result<(), String> {
+    let path = shortcuts_path(steam_root, account_id);
+    let mut root = load(&path)?;
+    let list = root.get_map_mut("shortcuts").ok_or("No shortcuts for this account")?;
+
+    let before = list.entries.len();
+    list.entries.retain(|(_, value)| match value {
+        BinaryValue::Map(entry) => entry_app_id(entry) != app_id,
+        _ => true,
+    });
+    if list.entries.len() == before {
+        return Err(format!("Shortcut {} not found", app_id));
+    }
+    for (index, (key, _)) in list.entries.iter_mut().enumerate() {
+        *key = index.to_string();
+    }
+
+    save(&path, &root)
+}
+
+fn load(path: &Path) -> Result<BinaryMap, String> {
+    if !path.exists() {
+        return Ok(BinaryMap::new());
+    }
+    let data = fs::read(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    vdf_binary::parse(&data)
+}
+
+fn save(path: &Path, root: &BinaryMap) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    config_backup::write_config(path, &vdf_binary::write(root))
+}
+
+// Stored appid when present, otherwise computed like Steam does
+fn entry_app_id(entry: &BinaryMap) -> u32 {
+    match entry.get_i32("appid") {
+        Some(id) if id != 0 => id as u32,
+        _ => shortcut_app_id(entry.get_str("Exe").unwrap_or_default(), entry.get_str("AppName").unwrap_or_default()),
+    }
+}
+
+fn to_shortcut(account_id: u32, entry: &BinaryMap) -> SteamShortcut {
+    let app_id = entry_app_id(entry);
+    SteamShortcut {
+        account_id,
+        account_name: None,
+        app_id,
+        game_id: shortcut_game_id(app_id),
+        app_name: entry.get_str("AppName").unwrap_or_default().to_string(),
+        exe: entry.get_str("Exe").unwrap_or_default().to_string(),
+        start_dir: entry.get_str("StartDir").unwrap_or_default().to_string(),
+        launch_options: entry.get_str("LaunchOptions").unwrap_or_default().to_string(),
+    }
+}
+
+fn quote(value: &str) -> String {
+    format!("\"{}\"", value.trim_matches('"'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn steam_root(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("steam_shortcuts_test_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        root
+    }
+
+    fn new_shortcut<'a>(app_name: &'a str, exe: &'a str) -> NewShortcut<'a> {
+        NewShortcut { app_name, exe, start_dir: None, launch_options: None }
+    }
+
+    #[test]
+    fn ids_match_steam() {
+        // CRC32 of "C:\Games\Game.exe" (quotes included) + "Portal" is 0x63b0de34
+        let app_id = shortcut_app_id("\"C:\\Games\\Game.exe\"", "Portal");
+        assert_eq!(app_id, 0xe3b0_de34);
+        assert_eq!(shortcut_game_id(app_id), 0xe3b0_de34_0200_0000);
+    }
+
+    #[test]
+    fn add_read_remove_renumbers() {
+        let root = steam_root("add_remove");
+        let account_id = 12345;
+        let first = add(&root, account_id, new_shortcut("First", "C:/Games/First/first.exe")).unwrap();
+        let second = add(&root, account_id, new_shortcut("Second", "C:/Games/Second/second.exe")).unwrap();
+        let third = add(&root, account_id, new_shortcut("Third", "C:/Games/Third/third.exe")).unwrap();
+
+        assert_eq!(second.start_dir, "\"C:/Games/Second\"");
+        let listed = read_account(&root, account_id).unwrap();
+        let names: Vec<&str> = listed.iter().map(|s| s.app_name.as_str()).collect();
+        assert_eq!(names, ["First", "Second", "Third"]);
+        assert_eq!(listed[0].app_id, first.app_id);
+
+        remove(&root, account_id, second.app_id).unwrap();
+        let saved = load(&shortcuts_path(&root, account_id)).unwrap();
+        let keys: Vec<&str> = saved.get_map("shortcuts").unwrap().entries.iter().map(|(k, _)| k.as_str()).collect();
+        let listed = read_account(&root, account_id).unwrap();
+        let _ = fs::remove_dir_all(&root);
+
+        assert_eq!(keys, ["0", "1"]);
+        let ids: Vec<u32> = listed.iter().map(|s| s.app_id).collect();
+        assert_eq!(ids, [first.app_id, third.app_id]);
+        assert!(remove(&root, account_id, second.app_id).is_err());
+    }
+
+    #[test]
+    fn validation_rejects_duplicates_and_missing_start_dir() {
+        let root = steam_root("validate");
+        let account_id = 12345;
+        add(&root, account_id, new_shortcut("Game", "C:/Games/game.exe")).unwrap();
+
+        let duplicate = validate(&root, account_id, &new_shortcut("Game", "C:/Games/game.exe"));
+        let no_folder = validate(&root, account_id, &new_shortcut("Other", "game.exe"));
+        let other = validate(&root, account_id, &new_shortcut("Other", "C:/Games/game.exe"));
+        let _ = fs::remove_dir_all(&root);
+
+        assert!(duplicate.unwrap_err().contains("already"));
+        assert!(no_folder.unwrap_err().starts_with("No start folder"));
+        assert!(other.is_ok());
+    }
+}
@@ -0,0 +1,139 @@
+use std::path::PathBuf;
+
+use crate::games::{self, Game, GameExtras, GameLibraryProvider};
+#[cfg(target_os = "windows")]
+use crate::library_cache;
+
+#[cfg(target_os = "windows")]
+use winreg::enums::*;
+#[cfg(target_os = "windows")]
+use winreg::RegKey;
+
+#[derive(Debug, Clone)]
+pub struct UbisoftGame {
+    /// Ubisoft Connect game id (subkey name under `Launcher\Installs`)
+    pub id: String,
+    pub name: String,
+    pub install_path: PathBuf,
+    pub executable: Option<PathBuf>,
+}
+
+pub fn launch_uri(game_id: &str) -> String {
+    format!("uplay://launch/{}/0", game_id)
+}
+
+pub struct UbisoftLibrary;
+
+impl GameLibraryProvider for UbisoftLibrary {
+    fn launcher_id(&self) -> &'static str {
+        "Ubisoft"
+    }
+
+    fn games(&self) -> Result<Vec<Game>, String> {
+        Ok(read_installed_games().into_iter().map(Game::from).collect())
+    }
+
+    fn launch(&self, native_id: &str) -> Result<(), String> {
+        games::open_launch_uri(&launch_uri(native_id))
+    }
+}
+
+impl From<UbisoftGame> for Game {
+    fn from(game: UbisoftGame) -> Self {
+        Game {
+            launcher_id: "Ubisoft".to_string(),
+            native_id: game.id,
+            name: game.name,
+            install_path: game.install_path.to_string_lossy().to_string(),
+            executables: game
+                .executable
+                .map(|p| p.to_string_lossy().to_string())
+                .into_iter()
+                .collect(),
+            size_on_disk: None,
+            last_played: None,
+            extras: GameExtras::Ubisoft {},
+        }
+    }
+}
+
+/// Games from `HKLM\SOFTWARE\WOW6432Node\Ubisoft\Launcher\Installs`.
+///
+/// The Installs key only has the folder; the display name and main exe come
+/// from the matching `Uplay Install <id>` uninstall entry when present.
+pub fn read_installed_games() -> Vec<UbisoftGame> {
+    #[cfg(target_os = "windows")]
+    {
+        let hklm = RegKey::predef(HKEY_LOCAL_MACHINE);
+        let Ok(installs) = hklm.open_subkey(r"SOFTWARE\WOW6432Node\Ubisoft\Launcher\Installs") else {
+            return Vec::new();
+        };
+
+        let mut games = Vec::new();
+        for id in installs.enum_keys().flatten() {
+            let Ok(game_key) = installs.open_subkey(&id) else { continue };
+            let Ok(install_dir) = game_key.get_value::<String, _>("InstallDir") else { continue };
+
+            // InstallDir uses forward slashes and a trailing slash
+            let install_path = PathBuf::from(install_dir.replace('/', "\\").trim_end_matches('\\'));
+            if !install_path.is_dir() {
+                continue;
+            }
+
+            let uninstall = hklm
+                .open_subkey(format!(
+                    r"SOFTWARE\WOW6432Node\Microsoft\Windows\CurrentVersion\Uninstall\Uplay Install {}",
+                    id
+                ))
+                .ok();
+            let display_name = uninstall
+                .as_ref()
+                .and_then(|key| key.get_value::<String, _>("DisplayName").ok());
+            // DisplayIcon usually points at the game exe
+            let icon_exe = uninstall
+                .as_ref()
+                .and_then(|key| key.get_value::<String, _>("DisplayIcon").ok())
+                .map(|icon| PathBuf::from(icon.trim_matches('"').split(',').next().unwrap_or_default()))
+                .filter(|path| path.is_file() && is_exe(path));
+
+            let name = display_name.unwrap_or_else(|| {
+                install_path
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_else(|| id.clone())
+            });
+
+            games.push(UbisoftGame {
+                executable: icon_exe,
+                id,
+                name,
+                install_path,
+            });
+        }
+
+        // No usable DisplayIcon, fall back to the (cached) folder search
+        let unnamed: Vec<PathBuf> = games
+            .iter()
+            .filter(|game| game.executable.is_none())
+            .map(|game| game.install_path.clone())
+            .collect();
+        let guessed = library_cache::guess_main_executables(&unnamed);
+        for game in games.iter_mut().filter(|game| game.executable.is_none()) {
+            game.executable = guessed.get(&game.install_path).cloned();
+        }
+
+        games.sort_by_key(|g| g.name.to_lowercase());
+        games
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        Vec::new()
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn is_exe(path: &std::path::Path) -> bool {
+    path.extension()
+        .map(|ext| ext.eq_ignore_ascii_case("exe"))
+        .unwrap_or(false)
+}
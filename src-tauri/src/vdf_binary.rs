@@ -0,0 +1,344 @@
+// Binary KeyValues ("binary VDF") as used by `shortcuts.vdf` and `appinfo.vdf`.
+// Every entry is `<type byte><key>\0<value>`; maps end with `0x08`.
+
+const TYPE_MAP: u8 = 0x00;
+const TYPE_STRING: u8 = 0x01;
+const TYPE_INT32: u8 = 0x02;
+const TYPE_FLOAT32: u8 = 0x03;
+const TYPE_POINTER: u8 = 0x04;
+const TYPE_WIDE_STRING: u8 = 0x05;
+const TYPE_COLOR: u8 = 0x06;
+const TYPE_UINT64: u8 = 0x07;
+const TYPE_END: u8 = 0x08;
+const TYPE_INT64: u8 = 0x0A;
+// Some writers end maps with 0x0B instead of 0x08
+const TYPE_END_ALT: u8 = 0x0B;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum BinaryValue {
+    Map(BinaryMap),
+    String(String),
+    Int32(i32),
+    Float32(f32),
+    Pointer(u32),
+    WideString(String),
+    Color(u32),
+    UInt64(u64),
+    Int64(i64),
+}
+
+/// Ordered key/value list; keys may repeat and order matters for round-trips.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BinaryMap {
+    pub entries: Vec<(String, BinaryValue)>,
+}
+
+impl BinaryMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Case-insensitive lookup; Steam itself mixes `AppName` and `appname`.
+    pub fn get(&self, key: &str) -> Option<&BinaryValue> {
+        self.entries
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(key))
+            .map(|(_, v)| v)
+    }
+
+    pub fn get_str(&self, key: &str) -> Option<&str> {
+        match self.get(key)? {
+            BinaryValue::String(s) | BinaryValue::WideString(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn get_i32(&self, key: &str) -> Option<i32> {
+        match self.get(key)? {
+            BinaryValue::Int32(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    pub fn get_map(&self, key: &str) -> Option<&BinaryMap> {
+        match self.get(key)? {
+            BinaryValue::Map(m) => Some(m),
+            _ => None,
+        }
+    }
+
+    pub fn get_map_mut(&mut self, key: &str) -> Option<&mut BinaryMap> {
+        self.entries
+            .iter_mut()
+            .find(|(k, _)| k.eq_ignore_ascii_case(key))
+            .and_then(|(_, v)| match v {
+                BinaryValue::Map(m) => Some(m),
+                _ => None,
+            })
+    }
+
+    /// Replaces the first entry with this key, or appends a new one.
+    pub fn set(&mut self, key: &str, value: BinaryValue) {
+        match self.entries.iter_mut().find(|(k, _)| k.eq_ignore_ascii_case(key)) {
+            Some((_, existing)) => *existing = value,
+            None => self.entries.push((key.to_string(), value)),
+        }
+    }
+}
+
+// ============================================
+// READER
+// ============================================
+
+/// Parses a whole binary VDF document (the root map's entries).
+pub fn parse(data: &[u8]) -> Result<BinaryMap, String> {
+    let mut reader = Reader::new(data);
+    reader.read_map()
+}
+
+/// Streaming reader over binary KeyValues.
+pub struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+    // appinfo.vdf v29 stores keys as u32 indices into a shared string table
+    key_table: Option<&'a [String]>,
+}
+
+impl<'a> Reader<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { data, pos: 0, key_table: None }
+    }
+
+    pub fn with_key_table(data: &'a [u8], key_table: &'a [String]) -> Self {
+        Self { data, pos: 0, key_table: Some(key_table) }
+    }
+
+    /// Reads entries until the map's end marker (or the end of the data for
+    /// a root map without a trailing marker).
+    pub fn read_map(&mut self) -> Result<BinaryMap, String> {
+        let mut map = BinaryMap::new();
+
+        loop {
+            let Some(&value_type) = self.data.get(self.pos) else {
+                return Ok(map);
+            };
+            self.pos += 1;
+
+            if value_type == TYPE_END || value_type == TYPE_END_ALT {
+                return Ok(map);
+            }
+
+            let key = self.read_key()?;
+            let value = match value_type {
+                TYPE_MAP => BinaryValue::Map(self.read_map()?),
+                TYPE_STRING => BinaryValue::String(self.read_cstring()?),
+                TYPE_INT32 => BinaryValue::Int32(i32::from_le_bytes(self.read_array()?)),
+                TYPE_FLOAT32 => BinaryValue::Float32(f32::from_le_bytes(self.read_array()?)),
+                TYPE_POINTER => BinaryValue::Pointer(u32::from_le_bytes(self.read_array()?)),
+                TYPE_WIDE_STRING => BinaryValue::WideString(self.read_wide_string()?),
+                TYPE_COLOR => BinaryValue::Color(u32::from_le_bytes(self.read_array()?)),
+                TYPE_UINT64 => BinaryValue::UInt64(u64::from_le_bytes(self.read_array()?)),
+                TYPE_INT64 => BinaryValue::Int64(i64::from_le_bytes(self.read_array()?)),
+                other => {
+                    return Err(format!("Unknown binary VDF type 0x{:02x} at offset {}", other, self.pos - 1));
+                }
+            };
+            map.entries.push((key, value));
+        }
+    }
+
+    fn read_key(&mut self) -> Result<String, String> {
+        let Some(table) = self.key_table else {
+            return self.read_cstring();
+        };
+        let index = u32::from_le_bytes(self.read_array()?) as usize;
+        table
+            .get(index)
+            .cloned()
+            .ok_or_else(|| format!("Key index {} outside the string table", index))
+    }
+
+    fn read_array<const N: usize>(&mut self) -> Result<[u8; N], String> {
+        let bytes = self
+            .data
+            .get(self.pos..self.pos + N)
+            .ok_or_else(|| "Unexpected end of binary VDF".to_string())?;
+        self.pos += N;
+        let mut array = [0u8; N];
+        array.copy_from_slice(bytes);
+        Ok(array)
+    }
+
+    fn read_cstring(&mut self) -> Result<String, String> {
+        let rest = &self.data[self.pos.min(self.data.len())..];
+        let len = rest
+            .iter()
+            .position(|&b| b == 0)
+            .ok_or_else(|| "Unterminated string in binary VDF".to_string())?;
+        let text = String::from_utf8_lossy(&rest[..len]).to_string();
+        self.pos += len + 1;
+        Ok(text)
+    }
+
+    fn read_wide_string(&mut self) -> Result<String, String> {
+        let mut units = Vec::new();
+        loop {
+            let unit = u16::from_le_bytes(self.read_array()?);
+            if unit == 0 {
+                break;
+            }
+            units.push(unit);
+        }
+        Ok(String::from_utf16_lossy(&units))
+    }
+}
+
+// ============================================
+// WRITER
+// ============================================
+
+/// Serializes a root map, including its closing end marker.
+pub fn write(map: &BinaryMap) -> Vec<u8> {
+    let mut out = Vec::new();
+    write_map(map, &mut out);
+    out
+}
+
+fn write_map(map: &BinaryMap, out: &mut Vec<u8>) {
+    for (key, value) in &map.entries {
+        let value_type = match value {
+            BinaryValue::Map(_) => TYPE_MAP,
+            BinaryValue::String(_) => TYPE_STRING,
+            BinaryValue::Int32(_) => TYPE_INT32,
+            BinaryValue::Float32(_) => TYPE_FLOAT32,
+            BinaryValue::Pointer(_) => TYPE_POINTER,
+            BinaryValue::WideString(_) => TYPE_WIDE_STRING,
+            BinaryValue::Color(_) => TYPE_COLOR,
+            BinaryValue::UInt64(_) => TYPE_UINT64,
+            BinaryValue::Int64(_) => TYPE_INT64,
+        };
+        out.push(value_type);
+        write_cstring(key, out);
+
+        match value {
+            BinaryValue::Map(child) => write_map(child, out),
+            BinaryValue::String(s) => write_cstring(s, out),
+            BinaryValue::Int32(v) => out.extend_from_slice(&v.to_le_bytes()),
+            BinaryValue::Float32(v) => out.extend_from_slice(&v.to_le_bytes()),
+            BinaryValue::Pointer(v) | BinaryValue::Color(v) => out.extend_from_slice(&v.to_le_bytes()),
+            BinaryValue::WideString(s) => {
+                for unit in s.encode_utf16() {
+                    out.extend_from_slice(&unit.to_le_bytes());
+                }
+                out.extend_from_slice(&[0, 0]);
+            }
+            BinaryValue::UInt64(v) => out.extend_from_slice(&v.to_le_bytes()),
+            BinaryValue::Int64(v) => out.extend_from_slice(&v.to_le_bytes()),
+        }
+    }
+    out.push(TYPE_END);
+}
+
+fn write_cstring(text: &str, out: &mut Vec<u8>) {
+    out.extend_from_slice(text.as_bytes());
+    out.push(0);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // `shortcuts.vdf` with one entry, as Steam writes it
+    fn shortcuts_bytes() -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend_from_slice(b"\x00shortcuts\x00");
+        data.extend_from_slice(b"\x000\x00");
+        data.extend_from_slice(b"\x02appid\x00");
+        data.extend_from_slice(&(-1_234_567_i32).to_le_bytes());
+        data.extend_from_slice(b"\x01AppName\x00My Game\x00");
+        data.extend_from_slice(b"\x01Exe\x00\"C:\\Games\\game.exe\"\x00");
+        data.extend_from_slice(b"\x02IsHidden\x00\x00\x00\x00\x00");
+        data.extend_from_slice(b"\x00tags\x00\x010\x00favorite\x00\x08");
+        data.extend_from_slice(b"\x08\x08\x08");
+        data
+    }
+
+    #[test]
+    fn parses_shortcuts() {
+        let root = parse(&shortcuts_bytes()).unwrap();
+        let shortcut = root.get_map("shortcuts").and_then(|s| s.get_map("0")).unwrap();
+        assert_eq!(shortcut.get_i32("appid"), Some(-1_234_567));
+        assert_eq!(shortcut.get_str("appname"), Some("My Game"));
+        assert_eq!(shortcut.get_str("exe"), Some("\"C:\\Games\\game.exe\""));
+        assert_eq!(shortcut.get_map("tags").and_then(|t| t.get_str("0")), Some("favorite"));
+        assert_eq!(shortcut.get_i32("AppName"), None);
+    }
+
+    #[test]
+    fn round_trips_byte_for_byte() {
+        let data = shortcuts_bytes();
+        assert_eq!(write(&parse(&data).unwrap()), data);
+    }
+
+    #[test]
+    fn round_trips_every_value_type() {
+        let mut child = BinaryMap::new();
+        child.set("wide", BinaryValue::WideString("Çağrı".to_string()));
+        let map = BinaryMap {
+            entries: vec![
+                ("map".to_string(), BinaryValue::Map(child)),
+                ("string".to_string(), BinaryValue::String("text".to_string())),
+                ("int32".to_string(), BinaryValue::Int32(-7)),
+                ("float".to_string(), BinaryValue::Float32(1.5)),
+                ("pointer".to_string(), BinaryValue::Pointer(0xDEAD_BEEF)),
+                ("color".to_string(), BinaryValue::Color(0x00FF_00FF)),
+                ("uint64".to_string(), BinaryValue::UInt64(u64::MAX)),
+                ("int64".to_string(), BinaryValue::Int64(i64::MIN)),
+                // Duplicate keys are kept in order
+                ("string".to_string(), BinaryValue::String("again".to_string())),
+            ],
+        };
+        assert_eq!(parse(&write(&map)).unwrap(), map);
+    }
+
+    #[test]
+    fn set_replaces_first_match_or_appends() {
+        let mut map = BinaryMap::new();
+        map.set("AppName", BinaryValue::String("Old".to_string()));
+        map.set("appname", BinaryValue::String("New".to_string()));
+        map.set("LaunchOptions", BinaryValue::String("-windowed".to_string()));
+        assert_eq!(map.entries.len(), 2);
+        assert_eq!(map.entries[0].0, "AppName");
+        assert_eq!(map.get_str("APPNAME"), Some("New"));
+    }
+
+    #[test]
+    fn reads_keys_from_string_table() {
+        let table = vec!["appinfo".to_string(), "name".to_string()];
+        let mut data = vec![TYPE_MAP];
+        data.extend_from_slice(&0u32.to_le_bytes());
+        data.push(TYPE_STRING);
+        data.extend_from_slice(&1u32.to_le_bytes());
+        data.extend_from_slice(b"Half-Life\x00");
+        data.extend_from_slice(&[TYPE_END_ALT, TYPE_END]);
+
+        let root = Reader::with_key_table(&data, &table).read_map().unwrap();
+        assert_eq!(root.get_map("appinfo").and_then(|m| m.get_str("name")), Some("Half-Life"));
+
+        let mut bad_index = vec![TYPE_STRING];
+        bad_index.extend_from_slice(&9u32.to_le_bytes());
+        bad_index.extend_from_slice(b"x\x00");
+        assert!(Reader::with_key_table(&bad_index, &table).read_map().is_err());
+    }
+
+    #[test]
+    fn rejects_truncated_and_unknown_data() {
+        let data = shortcuts_bytes();
+        // Cut right after the type byte of `tags`; cuts between entries read as a shorter map
+        assert!(parse(&data[..data.len() - 20]).is_err());
+        assert!(parse(b"\x02appid\x00\x01\x02").is_err());
+        assert!(parse(b"\x01name\x00no terminator").is_err());
+        assert!(parse(b"\x05wide\x00a\x00b").is_err());
+        assert!(parse(b"\x09weird\x00").is_err());
+    }
+}
@@ -0,0 +1,525 @@
+// Text KeyValues ("VDF") as used by `localconfig.vdf`, `libraryfolders.vdf`
+// and `loginusers.vdf`. keyvalues-serde needs a fixed shape; these files are
+// large, loosely cased and full of keys we don't model, so this reads them
+// into a plain tree instead. `VdfDocument` edits a file in place, keeping
+// everything it doesn't touch byte for byte.
+
+use std::ops::Range;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TextValue {
+    String(String),
+    Map(TextMap),
+}
+
+/// Ordered key/value list; keys may repeat.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TextMap {
+    pub entries: Vec<(String, TextValue)>,
+}
+
+impl TextMap {
+    /// Case-insensitive lookup; Steam writes both `Apps` and `apps`.
+    pub fn get(&self, key: &str) -> Option<&TextValue> {
+        self.entries
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(key))
+            .map(|(_, v)| v)
+    }
+
+    pub fn get_str(&self, key: &str) -> Option<&str> {
+        match self.get(key)? {
+            TextValue::String(s) => Some(s),
+            TextValue::Map(_) => None,
+        }
+    }
+
+    pub fn get_map(&self, key: &str) -> Option<&TextMap> {
+        match self.get(key)? {
+            TextValue::Map(m) => Some(m),
+            TextValue::String(_) => None,
+        }
+    }
+
+    /// Follows a chain of nested maps, e.g. `["Software", "Valve", "Steam"]`.
+    pub fn get_path(&self, path: &[&str]) -> Option<&TextMap> {
+        path.iter().try_fold(self, |map, key| map.get_map(key))
+    }
+
+    /// Child maps only, skipping string values.
+    pub fn maps(&self) -> impl Iterator<Item = (&str, &TextMap)> {
+        self.entries.iter().filter_map(|(k, v)| match v {
+            TextValue::Map(m) => Some((k.as_str(), m)),
+            TextValue::String(_) => None,
+        })
+    }
+}
+
+/// Parses a whole document. The result holds the top-level entries, so
+/// `localconfig.vdf` yields a map with a single `UserLocalConfigStore` key.
+pub fn parse(text: &str) -> Result<TextMap, String> {
+    Ok(to_map(&parse_spans(text)?))
+}
+
+fn to_map(entries: &[Entry]) -> TextMap {
+    TextMap {
+        entries: entries
+            .iter()
+            .map(|entry| {
+                let value = match &entry.value {
+                    EntryValue::String { value, .. } => TextValue::String(value.clone()),
+                    EntryValue::Map { entries, .. } => TextValue::Map(to_map(entries)),
+                };
+                (entry.key.clone(), value)
+            })
+            .collect(),
+    }
+}
+
+// ============================================
+// DOCUMENT (formatting-preserving editor)
+// ============================================
+
+/// A text VDF file that can be edited without reformatting it: values are
+/// replaced in place, new keys copy their siblings' indentation and removed
+/// keys take their whole line with them.
+#[derive(Debug, Clone)]
+pub struct VdfDocument {
+    text: String,
+    entries: Vec<Entry>,
+}
+
+impl VdfDocument {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        Ok(Self { text: text.to_string(), entries: parse_spans(text)? })
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    /// String value at `path` (case-insensitive keys).
+    pub fn get_str(&self, path: &[&str]) -> Option<&str> {
+        match &find(&self.entries, path)?.value {
+            EntryValue::String { value, .. } => Some(value),
+            EntryValue::Map { .. } => None,
+        }
+    }
+
+    /// "1" / "0" flags
+    pub fn get_bool(&self, path: &[&str]) -> Option<bool> {
+        match self.get_str(path)?.trim() {
+            "1" => Some(true),
+            "0" => Some(false),
+            _ => None,
+        }
+    }
+
+    /// Keys of the map at `path`; the top-level keys for an empty path.
+    pub fn keys(&self, path: &[&str]) -> Vec<String> {
+        let entries = if path.is_empty() {
+            &self.entries
+        } else {
+            match find(&self.entries, path).map(|entry| &entry.value) {
+                Some(EntryValue::Map { entries, .. }) => entries,
+                _ => return Vec::new(),
+            }
+        };
+        entries.iter().map(|entry| entry.key.clone()).collect()
+    }
+
+    /// Sets a string value, adding the key to its parent map when missing.
+    /// The parent map itself must exist.
+    pub fn set_str(&mut self, path: &[&str], value: &str) -> Result<(), String> {
+        let (key, parent_path) = path.split_last().ok_or("Empty VDF path")?;
+        let quoted = format!("\"{}\"", escape(value));
+
+        if let Some(entry) = find(&self.entries, path) {
+            let EntryValue::String { span, .. } = &entry.value else {
+                return Err(format!("\"{}\" is a section, not a value", key));
+            };
+            let span = span.clone();
+            return self.splice(span, &quoted);
+        }
+
+        // Indentation of the parent section's own key line
+        let (siblings, close, parent_indent) = if parent_path.is_empty() {
+            (&self.entries, None, String::new())
+        } else {
+            match find(&self.entries, parent_path) {
+                Some(Entry { value: EntryValue::Map { entries, close }, span, .. }) => {
+                    (entries, Some(*close), self.indent_at(span.start))
+                }
+                _ => return Err(format!("Section \"{}\" not found", parent_path.join("/"))),
+            }
+        };
+
+        let newline = if self.text.contains("\r\n") { "\r\n" } else { "\n" };
+        let separator = siblings
+            .iter()
+            .find_map(|entry| match &entry.value {
+                EntryValue::String { span, .. } => Some(self.text[entry.key_end..span.start].to_string()),
+                EntryValue::Map { .. } => None,
+            })
+            .filter(|gap| gap.chars().all(|c| c == ' ' || c == '\t'))
+            .unwrap_or_else(|| "\t\t".to_string());
+        let line = format!("\"{}\"{}{}", escape(key), separator, quoted);
+
+        match (siblings.last(), close) {
+            // After the last sibling, on a new line with its indentation
+            (Some(last), _) => {
+                let indent = self.indent_at(last.span.start);
+                let at = last.span.end;
+                self.splice(at..at, &format!("{}{}{}", newline, indent, line))
+            }
+            // First key of an empty section
+            (None, Some(close)) => {
+                let line_start = self.line_start(close);
+                if self.text[line_start..close].trim().is_empty() {
+                    // '}' on its own line: new line right above it
+                    let inserted = format!("{}\t{}{}", parent_indent, line, newline);
+                    self.splice(line_start..line_start, &inserted)
+                } else {
+                    // "{ }" on one line: give the '}' its own line
+                    let inserted = format!("{}{}\t{}{}{}", newline, parent_indent, line, newline, parent_indent);
+                    self.splice(close..close, &inserted)
+                }
+            }
+            (None, None) => {
+                let mut inserted = String::new();
+                if !self.text.is_empty() && !self.text.ends_with('\n') {
+                    inserted.push_str(newline);
+                }
+                inserted.push_str(&line);
+                inserted.push_str(newline);
+                let at = self.text.len();
+                self.splice(at..at, &inserted)
+            }
+        }
+    }
+
+    pub fn set_bool(&mut self, path: &[&str], value: bool) -> Result<(), String> {
+        self.set_str(path, if value { "1" } else { "0" })
+    }
+
+    /// Removes a value or a whole section. Returns false when it didn't exist.
+    pub fn remove(&mut self, path: &[&str]) -> Result<bool, String> {
+        let Some(entry) = find(&self.entries, path) else {
+            return Ok(false);
+        };
+        let mut range = entry.span.clone();
+
+        // Take the whole line when the entry is alone on it
+        let line_start = self.line_start(range.start);
+        if self.text[line_start..range.start].trim().is_empty() {
+            let rest = &self.text[range.end..];
+            let line_end = rest.find('\n').map(|i| range.end + i + 1).unwrap_or(self.text.len());
+            if self.text[range.end..line_end].trim().is_empty() {
+                range = line_start..line_end;
+            }
+        }
+
+        self.splice(range, "")?;
+        Ok(true)
+    }
+
+    fn splice(&mut self, range: Range<usize>, replacement: &str) -> Result<(), String> {
+        let mut text = self.text.clone();
+        text.replace_range(range, replacement);
+        // Re-parse so spans stay valid; an edit that breaks the file is refused
+        self.entries = parse_spans(&text)?;
+        self.text = text;
+        Ok(())
+    }
+
+    fn line_start(&self, pos: usize) -> usize {
+        self.text[..pos].rfind('\n').map(|i| i + 1).unwrap_or(0)
+    }
+
+    // Leading whitespace of the line containing `pos`
+    fn indent_at(&self, pos: usize) -> String {
+        let start = self.line_start(pos);
+        self.text[start..pos]
+            .chars()
+            .take_while(|c| *c == ' ' || *c == '\t')
+            .collect()
+    }
+}
+
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn find<'a>(entries: &'a [Entry], path: &[&str]) -> Option<&'a Entry> {
+    let (first, rest) = path.split_first()?;
+    let entry = entries.iter().find(|entry| entry.key.eq_ignore_ascii_case(first))?;
+    if rest.is_empty() {
+        return Some(entry);
+    }
+    match &entry.value {
+        EntryValue::Map { entries, .. } => find(entries, rest),
+        EntryValue::String { .. } => None,
+    }
+}
+
+// ============================================
+// PARSER
+// ============================================
+
+#[derive(Debug, Clone)]
+struct Entry {
+    key: String,
+    key_end: usize,
+    /// From the key's first byte to the value's last one (its '}' for maps)
+    span: Range<usize>,
+    value: EntryValue,
+}
+
+#[derive(Debug, Clone)]
+enum EntryValue {
+    String { value: String, span: Range<usize> },
+    Map { entries: Vec<Entry>, close: usize },
+}
+
+fn parse_spans(text: &str) -> Result<Vec<Entry>, String> {
+    let mut tokens = Tokenizer::new(text);
+    parse_entries(&mut tokens, false).map(|(entries, _)| entries)
+}
+
+// Entries up to the closing '}' (nested) or the end of input; also returns
+// the '}' position
+fn parse_entries(tokens: &mut Tokenizer, nested: bool) -> Result<(Vec<Entry>, usize), String> {
+    let mut entries = Vec::new();
+
+    loop {
+        let (key, key_span) = match tokens.next()? {
+            None if nested => return Err("Unexpected end of VDF, missing '}'".to_string()),
+            None => return Ok((entries, tokens.pos)),
+            Some((Token::Close, span)) if nested => return Ok((entries, span.start)),
+            Some((Token::Close, _)) => return Err(format!("Unexpected '}}' on line {}", tokens.line)),
+            Some((Token::Open, _)) => return Err(format!("Unexpected '{{' on line {}", tokens.line)),
+            Some((Token::Text(key), span)) => (key, span),
+        };
+
+        let (value, end) = match tokens.next()? {
+            Some((Token::Open, _)) => {
+                let (children, close) = parse_entries(tokens, true)?;
+                (EntryValue::Map { entries: children, close }, close + 1)
+            }
+            Some((Token::Text(value), span)) => (EntryValue::String { value, span: span.clone() }, span.end),
+            _ => return Err(format!("Missing value for \"{}\" on line {}", key, tokens.line)),
+        };
+        entries.push(Entry { key, key_end: key_span.end, span: key_span.start..end, value });
+    }
+}
+
+enum Token {
+    Open,
+    Close,
+    Text(String),
+}
+
+struct Tokenizer<'a> {
+    text: &'a str,
+    pos: usize,
+    line: usize,
+}
+
+impl<'a> Tokenizer<'a> {
+    fn new(text: &'a str) -> Self {
+        // Skip a UTF-8 BOM
+        let pos = if text.starts_with('\u{feff}') { '\u{feff}'.len_utf8() } else { 0 };
+        Self { text, pos, line: 1 }
+    }
+
+    fn peek_char(&self) -> Option<char> {
+        self.text[self.pos..].chars().next()
+    }
+
+    fn next_char(&mut self) -> Option<char> {
+        let c = self.peek_char()?;
+        self.pos += c.len_utf8();
+        if c == '\n' {
+            self.line += 1;
+        }
+        Some(c)
+    }
+
+    fn next(&mut self) -> Result<Option<(Token, Range<usize>)>, String> {
+        loop {
+            let start = self.pos;
+            let Some(c) = self.next_char() else {
+                return Ok(None);
+            };
+            let token = match c {
+                c if c.is_whitespace() => continue,
+                '/' if self.peek_char() == Some('/') => {
+                    self.skip_until('\n');
+                    continue;
+                }
+                // Platform conditionals like [$WIN32] apply to the previous pair
+                '[' => {
+                    self.skip_until(']');
+                    continue;
+                }
+                '{' => Token::Open,
+                '}' => Token::Close,
+                '"' => Token::Text(self.quoted()?),
+                _ => Token::Text(self.unquoted(start)),
+            };
+            return Ok(Some((token, start..self.pos)));
+        }
+    }
+
+    fn quoted(&mut self) -> Result<String, String> {
+        let start_line = self.line;
+        let mut text = String::new();
+        while let Some(c) = self.next_char() {
+            match c {
+                '"' => return Ok(text),
+                '\\' => match self.next_char() {
+                    Some('n') => text.push('\n'),
+                    Some('t') => text.push('\t'),
+                    Some('"') => text.push('"'),
+                    Some('\\') => text.push('\\'),
+                    // Unknown escape (e.g. a Windows path): keep it as written
+                    Some(other) => {
+                        text.push('\\');
+                        text.push(other);
+                    }
+                    None => break,
+                },
+                c => text.push(c),
+            }
+        }
+        Err(format!("Unterminated string starting on line {}", start_line))
+    }
+
+    // Runs until whitespace or a delimiter; `start` is the first character's offset
+    fn unquoted(&mut self, start: usize) -> String {
+        while let Some(c) = self.peek_char() {
+            if c.is_whitespace() || matches!(c, '"' | '{' | '}') {
+                break;
+            }
+            self.next_char();
+        }
+        self.text[start..self.pos].to_string()
+    }
+
+    fn skip_until(&mut self, end: char) {
+        while let Some(c) = self.next_char() {
+            if c == end {
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOGIN_USERS: &str = "\"users\"\n{\n\t\"76561197960287930\"\n\t{\n\t\t\"AccountName\"\t\t\"gaben\"\n\t\t\"PersonaName\"\t\t\"Gabe \\\"G\\\" N\"\n\t\t\"RememberPassword\"\t\t\"1\"\n\t\t\"MostRecent\"\t\t\"1\"\n\t}\n\t\"76561197960287931\"\n\t{\n\t\t\"AccountName\"\t\t\"other\"\n\t\t\"MostRecent\"\t\t\"0\"\n\t}\n}\n";
+
+    #[test]
+    fn parses_into_a_tree() {
+        let map = parse(LOGIN_USERS).unwrap();
+        let user = map.get_path(&["USERS", "76561197960287930"]).unwrap();
+        assert_eq!(user.get_str("accountname"), Some("gaben"));
+        assert_eq!(user.get_str("PersonaName"), Some("Gabe \"G\" N"));
+        assert_eq!(map.get_map("users").unwrap().maps().count(), 2);
+    }
+
+    #[test]
+    fn unchanged_document_is_byte_identical() {
+        let inputs = [
+            LOGIN_USERS.to_string(),
+            LOGIN_USERS.replace('\n', "\r\n"),
+            format!("\u{feff}{}", LOGIN_USERS),
+            "// comment\n\"Root\" { key value \"path\" \"C:\\Games\\x\" [$WIN32] \"empty\" {} }".to_string(),
+            String::new(),
+        ];
+        for input in inputs {
+            assert_eq!(VdfDocument::parse(&input).unwrap().as_str(), input);
+        }
+    }
+
+    #[test]
+    fn set_replaces_only_the_value() {
+        let mut doc = VdfDocument::parse(LOGIN_USERS).unwrap();
+        doc.set_bool(&["users", "76561197960287931", "MostRecent"], true).unwrap();
+        doc.set_bool(&["users", "76561197960287930", "mostrecent"], false).unwrap();
+        let expected = LOGIN_USERS
+            .replace("\"MostRecent\"\t\t\"1\"", "\"MostRecent\"\t\t\"x\"")
+            .replace("\"MostRecent\"\t\t\"0\"", "\"MostRecent\"\t\t\"1\"")
+            .replace("\"MostRecent\"\t\t\"x\"", "\"MostRecent\"\t\t\"0\"");
+        assert_eq!(doc.as_str(), expected);
+    }
+
+    #[test]
+    fn set_appends_new_keys_after_their_siblings() {
+        let mut doc = VdfDocument::parse(&LOGIN_USERS.replace('\n', "\r\n")).unwrap();
+        doc.set_str(&["users", "76561197960287931", "PersonaName"], "a \"quoted\" \\ name").unwrap();
+        let expected = LOGIN_USERS
+            .replace(
+                "\"MostRecent\"\t\t\"0\"\n",
+                "\"MostRecent\"\t\t\"0\"\n\t\t\"PersonaName\"\t\t\"a \\\"quoted\\\" \\\\ name\"\n",
+            )
+            .replace('\n', "\r\n");
+        assert_eq!(doc.as_str(), expected);
+        assert_eq!(doc.get_str(&["users", "76561197960287931", "PersonaName"]), Some("a \"quoted\" \\ name"));
+        assert_eq!(doc.keys(&["users", "76561197960287931"]), vec!["AccountName", "MostRecent", "PersonaName"]);
+    }
+
+    #[test]
+    fn set_fills_empty_sections() {
+        let mut doc = VdfDocument::parse("\"Root\"\n{\n\t\"apps\"\n\t{\n\t}\n\t\"inline\" { }\n}\n").unwrap();
+        doc.set_str(&["Root", "apps", "LaunchOptions"], "-novid").unwrap();
+        doc.set_str(&["Root", "inline", "key"], "1").unwrap();
+        assert_eq!(
+            doc.as_str(),
+            "\"Root\"\n{\n\t\"apps\"\n\t{\n\t\t\"LaunchOptions\"\t\t\"-novid\"\n\t}\n\t\"inline\" { \n\t\t\"key\"\t\t\"1\"\n\t}\n}\n"
+        );
+    }
+
+    #[test]
+    fn set_refuses_sections_and_missing_parents() {
+        let mut doc = VdfDocument::parse(LOGIN_USERS).unwrap();
+        assert!(doc.set_str(&["users", "76561197960287930"], "1").is_err());
+        assert!(doc.set_str(&["users", "missing", "key"], "1").is_err());
+        assert!(doc.set_str(&[], "1").is_err());
+        assert_eq!(doc.as_str(), LOGIN_USERS);
+    }
+
+    #[test]
+    fn remove_takes_whole_lines_and_keeps_the_rest() {
+        let mut doc = VdfDocument::parse(LOGIN_USERS).unwrap();
+        assert!(doc.remove(&["users", "76561197960287930", "RememberPassword"]).unwrap());
+        assert_eq!(doc.as_str(), LOGIN_USERS.replace("\t\t\"RememberPassword\"\t\t\"1\"\n", ""));
+
+        assert!(doc.remove(&["users", "76561197960287931"]).unwrap());
+        let second = "\t\"76561197960287931\"\n\t{\n\t\t\"AccountName\"\t\t\"other\"\n\t\t\"MostRecent\"\t\t\"0\"\n\t}\n";
+        assert_eq!(
+            doc.as_str(),
+            LOGIN_USERS.replace("\t\t\"RememberPassword\"\t\t\"1\"\n", "").replace(second, "")
+        );
+
+        assert!(!doc.remove(&["users", "76561197960287931"]).unwrap());
+        assert_eq!(doc.keys(&["users"]), vec!["76561197960287930"]);
+    }
+
+    #[test]
+    fn remove_leaves_shared_lines_intact() {
+        let mut doc = VdfDocument::parse("\"Root\" { \"a\" \"1\" \"b\" \"2\" }\n").unwrap();
+        assert!(doc.remove(&["Root", "a"]).unwrap());
+        assert_eq!(doc.as_str(), "\"Root\" {  \"b\" \"2\" }\n");
+    }
+
+    #[test]
+    fn rejects_malformed_text() {
+        assert!(VdfDocument::parse("\"users\"\n{\n\t\"a\" \"1\"\n").is_err());
+        assert!(parse("\"key\" \"unterminated").is_err());
+        assert!(parse("}").is_err());
+        assert!(parse("\"key\"").is_err());
+    }
+}
@@ -4,11 +4,18 @@ import { open } from '@tauri-apps/plugin-dialog';
 import { ShieldAlert, CheckCircle, AlertCircle, XCircle, Settings as SettingsIcon } from 'lucide-react';
 import { useLanguage } from '../contexts/LanguageContext';
 
+interface DetectionCandidate {
+    path: string;
+    confidence: number;
+    evidence: ('Registry' | 'RunningProcess' | 'CommonPath' | 'PeMatch')[];
+}
+
 interface DetectedLauncher {
     id: string;
     name: string;
     path: string | null;
-    status: 'Found' | 'NotFound' | 'UserSkipped';
+    status: 'Found' | 'NotFound' | 'UserSkipped' | 'Ambiguous';
+    candidates: DetectionCandidate[];
 }
 
 interface SetupWizardProps {
@@ -71,6 +78,21 @@ export default function SetupWizard({ onComplete }: SetupWizardProps) {
         }
     };
 
+    const chooseCandidate = async (id: string, path: string) => {
+        try {
+            await invoke('save_custom_launcher_path', {
+                launcherId: id,
+                path
+            });
+            // Detection doesn't look at saved paths, so resolve it locally
+            setLaunchers(prev => prev.map(l =>
+                l.id === id ? { ...l, path, status: 'Found' as const } : l
+            ));
+        } catch (error) {
+            console.error('Failed to save path:', error);
+        }
+    };
+
     const skipLauncher = async (id: string) => {
         try {
             await invoke('skip_launcher', { launcherId: id });
@@ -105,7 +127,7 @@ export default function SetupWizard({ onComplete }: SetupWizardProps) {
         }
     };
 
-    const allResolved = launchers.every(l => l.status !== 'NotFound');
+    const allResolved = launchers.every(l => l.status !== 'NotFound' && l.status !== 'Ambiguous');
     const allFound = launchers.every(l => l.status === 'Found');
 
     // Scanning Phase
@@ -216,6 +238,13 @@ export default function SetupWizard({ onComplete }: SetupWizardProps) {
                                 borderColor: 'border-gray-500/30',
                                 emoji: '❌',
                                 label: strings.wizard.status.skipped
+                            },
+                            Ambiguous: {
+                                icon: <AlertCircle className="w-8 h-8 text-blue-400" />,
+                                bgColor: 'bg-blue-500/10',
+                                borderColor: 'border-blue-500/30',
+                                emoji: '🔀',
+                                label: strings.wizard.status.ambiguous
                             }
                         };
 
@@ -239,7 +268,7 @@ export default function SetupWizard({ onComplete }: SetupWizardProps) {
                                                     {config.label}
                                                 </span>
                                             </div>
-                                            {launcher.path && (
+                                            {launcher.path && launcher.status !== 'Ambiguous' && (
                                                 <p className="text-xs text-gray-500 font-mono truncate">
                                                     {launcher.path}
                                                 </p>
@@ -264,6 +293,31 @@ export default function SetupWizard({ onComplete }: SetupWizardProps) {
                                         </div>
                                     )}
                                 </div>
+
+                                {launcher.status === 'Ambiguous' && (
+                                    <div className="mt-4 space-y-2">
+                                        <p className="text-sm text-gray-400">{strings.wizard.actions.choose_candidate}</p>
+                                        {launcher.candidates.map((candidate) => (
+                                            <div
+                                                key={candidate.path}
+                                                className="flex items-center justify-between gap-4 p-3 rounded-lg bg-white/5"
+                                            >
+                                                <div className="min-w-0">
+                                                    <p className="text-xs text-gray-300 font-mono truncate">{candidate.path}</p>
+                                                    <p className="text-xs text-gray-500">
+                                                        {candidate.confidence}% · {candidate.evidence.join(', ')}
+                                                    </p>
+                                                </div>
+                                                <button
+                                                    onClick={() => chooseCandidate(launcher.id, candidate.path)}
+                                                    className="px-4 py-2 bg-primary hover:bg-primary/80 text-white rounded-lg text-sm font-bold transition-all whitespace-nowrap"
+                                                >
+                                                    {strings.wizard.actions.use_this}
+                                                </button>
+                                            </div>
+                                        ))}
+                                    </div>
+                                )}
                             </div>
                         );
                     })}
@@ -120,14 +120,17 @@ export const en = {
         status: {
             found: "Found",
             not_found: "Not Found",
-            skipped: "Not Installed"
+            skipped: "Not Installed",
+            ambiguous: "Multiple Found"
         },
         actions: {
             show_location: "Show Location",
             not_installed: "Not Installed",
             complete: "Complete Setup",
             skip: "Skip for Now (You can reopen from Settings)",
-            some_missing: "Some Launchers Missing"
+            some_missing: "Some Launchers Missing",
+            choose_candidate: "Several installs were found, pick the one to use:",
+            use_this: "Use This"
         },
         warning: {
             title: "Warning",
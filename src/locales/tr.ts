@@ -120,14 +120,17 @@ export const tr = {
         status: {
             found: "Bulundu",
             not_found: "Bulunamadı",
-            skipped: "Kurulu Değil"
+            skipped: "Kurulu Değil",
+            ambiguous: "Birden Fazla Bulundu"
         },
         actions: {
             show_location: "Konumunu Göster",
             not_installed: "Kurulu Değil",
             complete: "Kurulumu Tamamla",
             skip: "Şimdilik Atla (Settings'ten tekrar açabilirsiniz)",
-            some_missing: "Bazı Launcher'lar Eksik"
+            some_missing: "Bazı Launcher'lar Eksik",
+            choose_candidate: "Birden fazla kurulum bulundu, kullanılacak olanı seçin:",
+            use_this: "Bunu Kullan"
         },
         warning: {
             title: "Uyarı",